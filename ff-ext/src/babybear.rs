@@ -0,0 +1,214 @@
+use crate::{
+    helpers::{sqrt_ratio_generic, sqrt_tonelli_shanks_vartime},
+    ExtensionField,
+};
+use ff::{
+    derive::{
+        rand_core::RngCore,
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    },
+    Field,
+};
+use std::ops::Neg;
+
+mod fp {
+    use ff::PrimeField;
+
+    /// BabyBear prime field with modulus `2^31 - 2^27 + 1`.
+    #[derive(PrimeField)]
+    #[PrimeFieldModulus = "2013265921"]
+    #[PrimeFieldGenerator = "31"]
+    #[PrimeFieldReprEndianness = "little"]
+    pub struct BabyBear([u64; 1]);
+}
+
+pub use fp::BabyBear;
+
+/// Quartic extension `BabyBear[X] / (X^4 - W)` with `W = 11`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BabyBearExt4(pub [BabyBear; 4]);
+
+const W: u64 = 11;
+
+// 2-adicity of `p^4 - 1` and its odd part `T`.
+const S: u32 = 29;
+const T: [u64; 2] = [0x4bc0000a8c00000f, 0x62e08003];
+const T_PLUS_1_DIV_2: [u64; 2] = [0xa5e0000546000008, 0x31704001];
+
+impl BabyBearExt4 {
+    // Non-square, since its norm `-W` is a non-square in `BabyBear`.
+    const X: Self = Self([
+        BabyBear::ZERO,
+        BabyBear::ONE,
+        BabyBear::ZERO,
+        BabyBear::ZERO,
+    ]);
+}
+
+impl From<BabyBear> for BabyBearExt4 {
+    fn from(value: BabyBear) -> Self {
+        Self([value, BabyBear::ZERO, BabyBear::ZERO, BabyBear::ZERO])
+    }
+}
+
+impl From<u64> for BabyBearExt4 {
+    fn from(value: u64) -> Self {
+        BabyBear::from(value).into()
+    }
+}
+
+impl ConstantTimeEq for BabyBearExt4 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (0..4).fold(Choice::from(1), |acc, i| acc & self.0[i].ct_eq(&other.0[i]))
+    }
+}
+
+impl ConditionallySelectable for BabyBearExt4 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(std::array::from_fn(|i| {
+            BabyBear::conditional_select(&a.0[i], &b.0[i], choice)
+        }))
+    }
+}
+
+impl Neg for BabyBearExt4 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(Neg::neg))
+    }
+}
+
+fn add(lhs: &BabyBearExt4, rhs: &BabyBearExt4) -> BabyBearExt4 {
+    BabyBearExt4(std::array::from_fn(|i| lhs.0[i] + rhs.0[i]))
+}
+
+fn sub(lhs: &BabyBearExt4, rhs: &BabyBearExt4) -> BabyBearExt4 {
+    BabyBearExt4(std::array::from_fn(|i| lhs.0[i] - rhs.0[i]))
+}
+
+fn mul(lhs: &BabyBearExt4, rhs: &BabyBearExt4) -> BabyBearExt4 {
+    let w = BabyBear::from(W);
+    let [a0, a1, a2, a3] = lhs.0;
+    let [b0, b1, b2, b3] = rhs.0;
+    BabyBearExt4([
+        a0 * b0 + w * (a1 * b3 + a2 * b2 + a3 * b1),
+        a0 * b1 + a1 * b0 + w * (a2 * b3 + a3 * b2),
+        a0 * b2 + a1 * b1 + a2 * b0 + w * a3 * b3,
+        a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0,
+    ])
+}
+
+fn add_base(lhs: &BabyBearExt4, rhs: &BabyBear) -> BabyBearExt4 {
+    let mut out = *lhs;
+    out.0[0] += rhs;
+    out
+}
+
+fn sub_base(lhs: &BabyBearExt4, rhs: &BabyBear) -> BabyBearExt4 {
+    let mut out = *lhs;
+    out.0[0] -= rhs;
+    out
+}
+
+fn mul_base(lhs: &BabyBearExt4, rhs: &BabyBear) -> BabyBearExt4 {
+    BabyBearExt4(lhs.0.map(|value| value * rhs))
+}
+
+impl_ext_ops!(BabyBearExt4, BabyBear);
+
+impl Field for BabyBearExt4 {
+    const ZERO: Self = Self([BabyBear::ZERO; 4]);
+    const ONE: Self = Self([
+        BabyBear::ONE,
+        BabyBear::ZERO,
+        BabyBear::ZERO,
+        BabyBear::ZERO,
+    ]);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self(std::array::from_fn(|_| BabyBear::random(&mut rng)))
+    }
+
+    fn square(&self) -> Self {
+        mul(self, self)
+    }
+
+    fn double(&self) -> Self {
+        add(self, self)
+    }
+
+    // Writes `self = A + X * B` with `A, B` in `BabyBear[Y] / (Y^2 - W)` for
+    // `Y = X^2`, then `self^-1 = (A - X * B) / (A^2 - Y * B^2)`.
+    fn invert(&self) -> CtOption<Self> {
+        let w = BabyBear::from(W);
+        let [a0, a1, a2, a3] = self.0;
+        let n0 = a0.square() + w * a2.square() - w * (a1 * a3).double();
+        let n1 = (a0 * a2).double() - a1.square() - w * a3.square();
+        (n0.square() - w * n1.square()).invert().map(|norm_inv| {
+            let (m0, m1) = (n0 * norm_inv, -n1 * norm_inv);
+            Self([
+                a0 * m0 + w * a2 * m1,
+                -(a1 * m0 + w * a3 * m1),
+                a0 * m1 + a2 * m0,
+                -(a1 * m1 + a3 * m0),
+            ])
+        })
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        sqrt_ratio_generic(num, div, &Self::X)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        sqrt_tonelli_shanks_vartime(self, S, &T, &T_PLUS_1_DIV_2, &Self::X)
+    }
+}
+
+impl ExtensionField<BabyBear> for BabyBearExt4 {
+    const DEGREE: usize = 4;
+
+    fn from_bases(bases: &[BabyBear]) -> Self {
+        debug_assert_eq!(bases.len(), 4);
+        Self(bases.try_into().unwrap())
+    }
+
+    fn as_bases(&self) -> &[BabyBear] {
+        self.0.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        babybear::{BabyBear, BabyBearExt4},
+        ExtensionField,
+    };
+    use ff::{Field, PrimeField};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha12Rng};
+
+    #[test]
+    fn babybear_ext4() {
+        assert_eq!(BabyBear::from(2013265921), BabyBear::ZERO);
+        assert_eq!(BabyBear::NUM_BITS, 31);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        for _ in 0..16 {
+            let [a, b, c] = [(); 3].map(|_| BabyBearExt4::random(&mut rng));
+            let base = BabyBear::random(&mut rng);
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * base, a * BabyBearExt4::from(base));
+            assert_eq!(a * a.invert().unwrap(), BabyBearExt4::ONE);
+            let bases: &[BabyBear] = a.as_bases();
+            assert_eq!(BabyBearExt4::from_bases(bases), a);
+
+            let square = a.square();
+            assert_eq!(square.sqrt().unwrap().square(), square);
+            let non_square = square * BabyBearExt4::X;
+            let (is_square, sqrt) = BabyBearExt4::sqrt_ratio(&non_square, &b.square());
+            assert!(!bool::from(is_square));
+            assert_eq!(sqrt.square() * b.square(), non_square * BabyBearExt4::X);
+        }
+        assert!(bool::from(BabyBearExt4::ZERO.invert().is_none()));
+    }
+}
@@ -0,0 +1,52 @@
+use ff::{
+    derive::subtle::{Choice, ConditionallySelectable, CtOption},
+    Field,
+};
+
+/// Same as `ff::helpers::sqrt_ratio_generic` but with the given non-square
+/// `g_s`, for extension fields which don't implement `PrimeField`.
+pub(crate) fn sqrt_ratio_generic<F: Field>(num: &F, div: &F, g_s: &F) -> (Choice, F) {
+    let a = div.invert().unwrap_or(F::ZERO) * num;
+    let sqrt_a = a.sqrt();
+    let sqrt_b = (a * g_s).sqrt();
+    let is_square = sqrt_a.is_some();
+    (
+        is_square & (num.is_zero() | !div.is_zero()),
+        CtOption::conditional_select(&sqrt_b, &sqrt_a, is_square).unwrap(),
+    )
+}
+
+/// Variable-time Tonelli-Shanks for a field of order `2^s * t + 1`, where
+/// `tp1d2 = (t + 1) / 2` and `g_s` is a non-square.
+pub(crate) fn sqrt_tonelli_shanks_vartime<F: Field>(
+    f: &F,
+    s: u32,
+    t: &[u64],
+    tp1d2: &[u64],
+    g_s: &F,
+) -> CtOption<F> {
+    let mut m = s;
+    let mut c = g_s.pow_vartime(t);
+    let mut t = f.pow_vartime(t);
+    let mut r = f.pow_vartime(tp1d2);
+    while !bool::from(t.is_zero()) && t != F::ONE {
+        let i = (1..m)
+            .try_fold(t.square(), |t2i, i| {
+                if t2i == F::ONE {
+                    Err(i)
+                } else {
+                    Ok(t2i.square())
+                }
+            })
+            .err();
+        let Some(i) = i else {
+            return CtOption::new(F::ZERO, Choice::from(0));
+        };
+        let b = (0..m - i - 1).fold(c, |b, _| b.square());
+        m = i;
+        c = b.square();
+        t *= c;
+        r *= b;
+    }
+    CtOption::new(r, Choice::from(1))
+}
@@ -6,6 +6,13 @@ use std::{
 
 pub use ff;
 
+#[macro_use]
+mod macros;
+mod helpers;
+
+pub mod babybear;
+pub mod mersenne31;
+
 pub trait ExtensionField<F>:
     Field
     + From<F>
@@ -42,9 +49,12 @@ impl<F: Field> ExtensionField<F> for F {
     }
 }
 
-mod impl_goldilocks {
+/// Goldilocks field with its quadratic extension, the configuration used
+/// throughout the tests and benchmarks of dependent crates.
+pub mod goldilocks {
     use crate::ExtensionField;
-    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    pub use ::goldilocks::{Goldilocks, GoldilocksExt2};
 
     impl ExtensionField<Goldilocks> for GoldilocksExt2 {
         const DEGREE: usize = 2;
@@ -58,4 +68,25 @@ mod impl_goldilocks {
             self.0.as_slice()
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use crate::{
+            goldilocks::{Goldilocks, GoldilocksExt2},
+            ExtensionField,
+        };
+        use ff::Field;
+        use rand_chacha::{rand_core::SeedableRng, ChaCha12Rng};
+
+        #[test]
+        fn goldilocks_ext2() {
+            let mut rng = ChaCha12Rng::seed_from_u64(0);
+            let a = GoldilocksExt2::random(&mut rng);
+            let base = Goldilocks::random(&mut rng);
+            let bases: &[Goldilocks] = a.as_bases();
+            assert_eq!(bases.len(), 2);
+            assert_eq!(GoldilocksExt2::from_bases(bases), a);
+            assert_eq!(a * base, a * GoldilocksExt2::from(base));
+        }
+    }
 }
@@ -0,0 +1,67 @@
+// Implements the arithmetic traits of extension field `$ext` over `$base` in
+// terms of `add`, `sub`, `mul`, `add_base`, `sub_base` and `mul_base`, which
+// are expected to be in scope.
+macro_rules! impl_ext_ops {
+    ($ext:ident, $base:ty) => {
+        impl_ext_ops!(@ops $ext, $ext, add, sub, mul);
+        impl_ext_ops!(@ops $ext, $base, add_base, sub_base, mul_base);
+
+        impl ::std::iter::Sum for $ext {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as ::ff::Field>::ZERO, |acc, value| acc + value)
+            }
+        }
+
+        impl<'a> ::std::iter::Sum<&'a $ext> for $ext {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(<Self as ::ff::Field>::ZERO, |acc, value| acc + value)
+            }
+        }
+
+        impl ::std::iter::Product for $ext {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as ::ff::Field>::ONE, |acc, value| acc * value)
+            }
+        }
+
+        impl<'a> ::std::iter::Product<&'a $ext> for $ext {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(<Self as ::ff::Field>::ONE, |acc, value| acc * value)
+            }
+        }
+    };
+    (@ops $ext:ident, $rhs:ty, $add:ident, $sub:ident, $mul:ident) => {
+        impl_ext_ops!(@op $ext, $rhs, Add, add, AddAssign, add_assign, $add);
+        impl_ext_ops!(@op $ext, $rhs, Sub, sub, SubAssign, sub_assign, $sub);
+        impl_ext_ops!(@op $ext, $rhs, Mul, mul, MulAssign, mul_assign, $mul);
+    };
+    (@op $ext:ident, $rhs:ty, $op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $f:ident) => {
+        impl ::std::ops::$op<$rhs> for $ext {
+            type Output = Self;
+
+            fn $method(self, rhs: $rhs) -> Self {
+                $f(&self, &rhs)
+            }
+        }
+
+        impl<'a> ::std::ops::$op<&'a $rhs> for $ext {
+            type Output = Self;
+
+            fn $method(self, rhs: &'a $rhs) -> Self {
+                $f(&self, rhs)
+            }
+        }
+
+        impl ::std::ops::$op_assign<$rhs> for $ext {
+            fn $method_assign(&mut self, rhs: $rhs) {
+                *self = $f(self, &rhs)
+            }
+        }
+
+        impl<'a> ::std::ops::$op_assign<&'a $rhs> for $ext {
+            fn $method_assign(&mut self, rhs: &'a $rhs) {
+                *self = $f(self, rhs)
+            }
+        }
+    };
+}
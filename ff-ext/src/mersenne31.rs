@@ -0,0 +1,229 @@
+use crate::{
+    helpers::{sqrt_ratio_generic, sqrt_tonelli_shanks_vartime},
+    ExtensionField,
+};
+use ff::{
+    derive::{
+        rand_core::RngCore,
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    },
+    Field,
+};
+use std::ops::Neg;
+
+mod fp {
+    use ff::PrimeField;
+
+    /// Mersenne31 prime field with modulus `2^31 - 1`.
+    #[derive(PrimeField)]
+    #[PrimeFieldModulus = "2147483647"]
+    #[PrimeFieldGenerator = "7"]
+    #[PrimeFieldReprEndianness = "little"]
+    pub struct Mersenne31([u64; 1]);
+}
+
+pub use fp::Mersenne31;
+
+/// Quartic extension `CM31[U] / (U^2 - 2 - I)` over the complex extension
+/// `CM31 = Mersenne31[I] / (I^2 + 1)`, with bases `[a, b, c, d]` standing for
+/// `(a + b * I) + (c + d * I) * U`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mersenne31Ext4(pub [Mersenne31; 4]);
+
+// 2-adicity of `p^4 - 1` and its odd part `T`.
+const S: u32 = 33;
+const T: [u64; 2] = [0xc0000000bfffffff, 0x7ffffff];
+const T_PLUS_1_DIV_2: [u64; 2] = [0xe000000060000000, 0x3ffffff];
+
+impl Mersenne31Ext4 {
+    // Non-square, since `U^2 = 2 + I` is a non-square in `CM31`.
+    const U: Self = Self([
+        Mersenne31::ZERO,
+        Mersenne31::ZERO,
+        Mersenne31::ONE,
+        Mersenne31::ZERO,
+    ]);
+}
+
+type Cm31 = (Mersenne31, Mersenne31);
+
+fn cm31_mul((a, b): Cm31, (c, d): Cm31) -> Cm31 {
+    (a * c - b * d, a * d + b * c)
+}
+
+// Multiplies by `U^2 = 2 + I`.
+fn cm31_mul_by_u2((a, b): Cm31) -> Cm31 {
+    (a.double() - b, a + b.double())
+}
+
+impl From<Mersenne31> for Mersenne31Ext4 {
+    fn from(value: Mersenne31) -> Self {
+        Self([value, Mersenne31::ZERO, Mersenne31::ZERO, Mersenne31::ZERO])
+    }
+}
+
+impl From<u64> for Mersenne31Ext4 {
+    fn from(value: u64) -> Self {
+        Mersenne31::from(value).into()
+    }
+}
+
+impl ConstantTimeEq for Mersenne31Ext4 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (0..4).fold(Choice::from(1), |acc, i| acc & self.0[i].ct_eq(&other.0[i]))
+    }
+}
+
+impl ConditionallySelectable for Mersenne31Ext4 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(std::array::from_fn(|i| {
+            Mersenne31::conditional_select(&a.0[i], &b.0[i], choice)
+        }))
+    }
+}
+
+impl Neg for Mersenne31Ext4 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(Neg::neg))
+    }
+}
+
+fn add(lhs: &Mersenne31Ext4, rhs: &Mersenne31Ext4) -> Mersenne31Ext4 {
+    Mersenne31Ext4(std::array::from_fn(|i| lhs.0[i] + rhs.0[i]))
+}
+
+fn sub(lhs: &Mersenne31Ext4, rhs: &Mersenne31Ext4) -> Mersenne31Ext4 {
+    Mersenne31Ext4(std::array::from_fn(|i| lhs.0[i] - rhs.0[i]))
+}
+
+fn mul(lhs: &Mersenne31Ext4, rhs: &Mersenne31Ext4) -> Mersenne31Ext4 {
+    let [a0, a1, a2, a3] = lhs.0;
+    let [b0, b1, b2, b3] = rhs.0;
+    let (x0, y0) = cm31_mul((a0, a1), (b0, b1));
+    let (x1, y1) = cm31_mul_by_u2(cm31_mul((a2, a3), (b2, b3)));
+    let (x2, y2) = cm31_mul((a0, a1), (b2, b3));
+    let (x3, y3) = cm31_mul((a2, a3), (b0, b1));
+    Mersenne31Ext4([x0 + x1, y0 + y1, x2 + x3, y2 + y3])
+}
+
+fn add_base(lhs: &Mersenne31Ext4, rhs: &Mersenne31) -> Mersenne31Ext4 {
+    let mut out = *lhs;
+    out.0[0] += rhs;
+    out
+}
+
+fn sub_base(lhs: &Mersenne31Ext4, rhs: &Mersenne31) -> Mersenne31Ext4 {
+    let mut out = *lhs;
+    out.0[0] -= rhs;
+    out
+}
+
+fn mul_base(lhs: &Mersenne31Ext4, rhs: &Mersenne31) -> Mersenne31Ext4 {
+    Mersenne31Ext4(lhs.0.map(|value| value * rhs))
+}
+
+impl_ext_ops!(Mersenne31Ext4, Mersenne31);
+
+impl Field for Mersenne31Ext4 {
+    const ZERO: Self = Self([Mersenne31::ZERO; 4]);
+    const ONE: Self = Self([
+        Mersenne31::ONE,
+        Mersenne31::ZERO,
+        Mersenne31::ZERO,
+        Mersenne31::ZERO,
+    ]);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self(std::array::from_fn(|_| Mersenne31::random(&mut rng)))
+    }
+
+    fn square(&self) -> Self {
+        mul(self, self)
+    }
+
+    fn double(&self) -> Self {
+        add(self, self)
+    }
+
+    // Writes `self = A + U * B` with `A, B` in `CM31`, then
+    // `self^-1 = (A - U * B) / (A^2 - U^2 * B^2)`.
+    fn invert(&self) -> CtOption<Self> {
+        let [a0, a1, a2, a3] = self.0;
+        let (a, b) = ((a0, a1), (a2, a3));
+        let (aa, ubb) = (cm31_mul(a, a), cm31_mul_by_u2(cm31_mul(b, b)));
+        let (n0, n1) = (aa.0 - ubb.0, aa.1 - ubb.1);
+        (n0.square() + n1.square()).invert().map(|norm_inv| {
+            let n_inv = (n0 * norm_inv, -n1 * norm_inv);
+            let (x0, x1) = cm31_mul(a, n_inv);
+            let (y0, y1) = cm31_mul(b, n_inv);
+            Self([x0, x1, -y0, -y1])
+        })
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        sqrt_ratio_generic(num, div, &Self::U)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        sqrt_tonelli_shanks_vartime(self, S, &T, &T_PLUS_1_DIV_2, &Self::U)
+    }
+}
+
+impl ExtensionField<Mersenne31> for Mersenne31Ext4 {
+    const DEGREE: usize = 4;
+
+    fn from_bases(bases: &[Mersenne31]) -> Self {
+        debug_assert_eq!(bases.len(), 4);
+        Self(bases.try_into().unwrap())
+    }
+
+    fn as_bases(&self) -> &[Mersenne31] {
+        self.0.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        mersenne31::{Mersenne31, Mersenne31Ext4},
+        ExtensionField,
+    };
+    use ff::{Field, PrimeField};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha12Rng};
+
+    #[test]
+    fn mersenne31_ext4() {
+        assert_eq!(Mersenne31::from((1 << 31) - 1), Mersenne31::ZERO);
+        assert_eq!(Mersenne31::NUM_BITS, 31);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        for _ in 0..16 {
+            let [a, b, c] = [(); 3].map(|_| Mersenne31Ext4::random(&mut rng));
+            let base = Mersenne31::random(&mut rng);
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!((a * b) * c, a * (b * c));
+            assert_eq!(a * base, a * Mersenne31Ext4::from(base));
+            assert_eq!(a * a.invert().unwrap(), Mersenne31Ext4::ONE);
+            let bases: &[Mersenne31] = a.as_bases();
+            assert_eq!(Mersenne31Ext4::from_bases(bases), a);
+
+            let square = a.square();
+            assert_eq!(square.sqrt().unwrap().square(), square);
+            let non_square = square * Mersenne31Ext4::U;
+            let (is_square, sqrt) = Mersenne31Ext4::sqrt_ratio(&non_square, &b.square());
+            assert!(!bool::from(is_square));
+            assert_eq!(sqrt.square() * b.square(), non_square * Mersenne31Ext4::U);
+        }
+        assert_eq!(
+            Mersenne31Ext4::U.square(),
+            Mersenne31Ext4([
+                Mersenne31::from(2),
+                Mersenne31::ONE,
+                Mersenne31::ZERO,
+                Mersenne31::ZERO
+            ])
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::Measurement, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use gkr::{
+    circuit::{node::EvalClaim, Circuit},
+    poly::{box_dense_poly, BoxMultilinearPoly, MultilinearPoly},
+    prove_gkr,
+    transcript::StdRngTranscript,
+    util::{
+        arithmetic::{ExtensionField, PrimeField},
+        chain,
+        dev::{rand_vec, seeded_std_rng},
+        Itertools, RngCore,
+    },
+    verify_gkr,
+};
+use gkr_gadget::range::decompose;
+use goldilocks::{Goldilocks, GoldilocksExt2};
+use halo2_curves::bn256;
+use rayon::ThreadPoolBuilder;
+
+const NUM_BITS: usize = 32;
+
+type Setup<F, E> = (
+    Circuit<F, E>,
+    Vec<BoxMultilinearPoly<'static, F, E>>,
+    Vec<EvalClaim<E>>,
+);
+
+// Decomposes `2^log2_size` random `NUM_BITS`-bit values into limbs of
+// `limb_bits` bits, each limb looked up in a table of size `2^limb_bits`.
+fn setup<F: PrimeField, E: ExtensionField<F>>(log2_size: usize, limb_bits: usize) -> Setup<F, E> {
+    let mut rng = seeded_std_rng();
+    let mut circuit = Circuit::default();
+    let (_, decomposition, _) = decompose(&mut circuit, log2_size, NUM_BITS, limb_bits).unwrap();
+
+    let values = (0..1 << log2_size)
+        .map(|_| rng.next_u64() >> (64 - NUM_BITS))
+        .collect_vec();
+    let (limbs, m, t) = decomposition.assign::<F>(&values).unwrap();
+    let inputs = chain![
+        [values.into_iter().map(F::from).collect_vec()],
+        limbs,
+        [m, t]
+    ]
+    .map(box_dense_poly)
+    .collect();
+    let values = circuit.evaluate(inputs);
+    let output_claims = circuit
+        .outputs()
+        .map(|idx| {
+            let point = rand_vec(circuit.nodes()[idx].log2_output_size(), &mut rng);
+            let value = values[idx].evaluate(&point);
+            EvalClaim::new(point, value)
+        })
+        .collect();
+    (circuit, values, output_claims)
+}
+
+fn run_range<F: PrimeField, E: ExtensionField<F>>(
+    field_name: &str,
+    group: &mut BenchmarkGroup<impl Measurement>,
+) {
+    let serial = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    for limb_bits in [8, 16] {
+        let log2_num_limbs = NUM_BITS.div_ceil(limb_bits).ilog2() as usize;
+        for log2_num_lookups in (16..=24).step_by(2) {
+            let (circuit, values, output_claims) =
+                setup::<F, E>(log2_num_lookups - log2_num_limbs, limb_bits);
+            let prove_once = || {
+                let mut transcript = StdRngTranscript::default();
+                prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+                transcript.into_proof()
+            };
+
+            let name = format!("{field_name}/limb_bits_{limb_bits}");
+            let id = BenchmarkId::new(format!("prove/{name}"), log2_num_lookups);
+            group.bench_function(id, |b| b.iter(prove_once));
+            let id = BenchmarkId::new(format!("prove_serial/{name}"), log2_num_lookups);
+            group.bench_function(id, |b| b.iter(|| serial.install(prove_once)));
+
+            let proof = prove_once();
+            let id = BenchmarkId::new(format!("verify/{name}"), log2_num_lookups);
+            group.bench_function(id, |b| {
+                b.iter(|| {
+                    let mut transcript = StdRngTranscript::from_proof(&proof);
+                    verify_gkr(&circuit, &output_claims, &mut transcript).unwrap();
+                })
+            });
+        }
+    }
+}
+
+fn bench_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("range");
+    group.sample_size(10);
+
+    run_range::<Goldilocks, GoldilocksExt2>("goldilocks_qe", &mut group);
+    run_range::<bn256::Fr, bn256::Fr>("bn254", &mut group);
+}
+
+criterion_group!(bench, bench_range);
+criterion_main!(bench);
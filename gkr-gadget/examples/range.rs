@@ -0,0 +1,189 @@
+//! Range-checks a JSON array of `u64`s to 32 bits and writes the proof, or
+//! verifies a written proof against the same array.
+//!
+//! ```text
+//! cargo run --release --example range -- prove values.json proof.bin
+//! cargo run --release --example range -- verify values.json proof.bin
+//! ```
+//!
+//! The values and the limb table are public inputs. The limbs and
+//! multiplicities are committed with [`TrivialPcs`], whose commitments are
+//! written to the proof ahead of the GKR proof, and the claims left on them
+//! are opened at the end of it.
+
+use gkr::{
+    circuit::{node::EvalClaim, Circuit},
+    ff_ext::goldilocks::{Goldilocks, GoldilocksExt2},
+    pcs::{MultilinearPcs, OpeningAccumulator, TrivialPcs},
+    poly::{box_dense_poly, BoxMultilinearPoly},
+    prove_gkr_with_public_io,
+    transcript::{Keccak256Transcript, TranscriptRead, TranscriptWrite},
+    util::{arithmetic::Field, chain, dev::std_rng, izip, Itertools},
+    verify_gkr_with_public_io, Error,
+};
+use gkr_gadget::range::decompose;
+use std::{env, fs, process};
+
+const NUM_BITS: usize = 32;
+const LIMB_BITS: usize = 16;
+
+type F = Goldilocks;
+type E = GoldilocksExt2;
+type Pcs = TrivialPcs<F, E>;
+
+fn main() {
+    let args = env::args().collect_vec();
+    let [_, command, values_path, proof_path] = args.as_slice() else {
+        exit("Usage: range <prove|verify> <values.json> <proof.bin>");
+    };
+    let values = fs::read_to_string(values_path).unwrap_or_else(|err| exit(err));
+    let values: Vec<u64> = serde_json::from_str(&values).unwrap_or_else(|err| exit(err));
+    if !values.len().is_power_of_two() {
+        exit("Expected a power-of-two number of values");
+    }
+    if values.iter().any(|value| value >> NUM_BITS != 0) {
+        exit(format!("Expected values of at most {NUM_BITS} bits"));
+    }
+
+    let mut circuit = Circuit::<F, E>::default();
+    let log2_size = values.len().ilog2() as usize;
+    let (value, decomposition, decomposed) =
+        decompose(&mut circuit, log2_size, NUM_BITS, LIMB_BITS)
+            .unwrap_or_else(|err| exit(format!("{err:?}")));
+    let (value, t): (usize, usize) = (value.into(), decomposed.t.into());
+    let private_inputs = circuit
+        .inputs()
+        .filter(|idx| ![value, t].contains(idx))
+        .collect_vec();
+    let num_vars = private_inputs
+        .iter()
+        .map(|idx| circuit.nodes()[*idx].log2_output_size())
+        .max()
+        .unwrap_or_default();
+    let (pp, vp) = Pcs::setup(num_vars, std_rng())
+        .and_then(|param| Pcs::trim(&param, num_vars))
+        .unwrap_or_else(|err| exit(format!("{err:?}")));
+
+    match command.as_str() {
+        "prove" => {
+            let (limbs, m, table) = decomposition
+                .assign::<F>(&values)
+                .unwrap_or_else(|err| exit(format!("{err:?}")));
+            let inputs = chain![
+                [values.into_iter().map(F::from).collect()],
+                limbs,
+                [m, table]
+            ]
+            .map(box_dense_poly)
+            .collect();
+            let values = circuit.evaluate(inputs);
+
+            let proof = prove(&circuit, &values, [value, t], &private_inputs, &pp)
+                .unwrap_or_else(|err| exit(format!("{err:?}")));
+            fs::write(proof_path, &proof).unwrap_or_else(|err| exit(err));
+            println!("Wrote proof of {} bytes to {proof_path}", proof.len());
+        }
+        "verify" => {
+            let proof = fs::read(proof_path).unwrap_or_else(|err| exit(err));
+            let public_inputs = [
+                (value, values.into_iter().map(F::from).collect()),
+                (t, (0..1 << LIMB_BITS).map(F::from).collect()),
+            ];
+            let outputs = circuit
+                .outputs()
+                .map(|idx| vec![F::ZERO; circuit.nodes()[idx].output_size()])
+                .collect_vec();
+
+            let num_claims = verify(
+                &circuit,
+                &public_inputs,
+                &outputs,
+                &private_inputs,
+                &vp,
+                &proof,
+            )
+            .unwrap_or_else(|err| exit(format!("Invalid proof: {err:?}")));
+            println!("Verified, with {num_claims} claims on private inputs opened");
+        }
+        _ => exit(format!("Unknown command {command}")),
+    }
+}
+
+// Writes the commitments to `private_inputs`, then proves `circuit` and opens
+// the claims left on them.
+fn prove(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    public_inputs: [usize; 2],
+    private_inputs: &[usize],
+    pp: &usize,
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = Keccak256Transcript::new(Vec::new());
+    let mut comms = Vec::new();
+    for idx in private_inputs {
+        let comm = Pcs::commit(pp, &*values[*idx])?;
+        TranscriptWrite::<F, E>::write_felts(&mut transcript, &comm)?;
+        comms.push(comm);
+    }
+
+    let input_claims = prove_gkr_with_public_io(circuit, values, &public_inputs, &mut transcript)?;
+    let poly = |comm: &Vec<F>| {
+        let idx = comms.iter().position(|c| c == comm)?;
+        Some(&*values[private_inputs[idx]])
+    };
+    accumulate(circuit, private_inputs, &comms, input_claims).open::<F, Pcs>(
+        pp,
+        poly,
+        &mut transcript,
+    )?;
+    Ok(transcript.into_proof())
+}
+
+// Reads the commitments to `private_inputs`, then verifies `proof` and the
+// openings of the claims left on them, returning the number of claims opened.
+fn verify(
+    circuit: &Circuit<F, E>,
+    public_inputs: &[(usize, Vec<F>)],
+    outputs: &[Vec<F>],
+    private_inputs: &[usize],
+    vp: &usize,
+    proof: &[u8],
+) -> Result<usize, Error> {
+    let mut transcript = Keccak256Transcript::from_proof(proof);
+    let mut comms = Vec::new();
+    for idx in private_inputs {
+        let size = circuit.nodes()[*idx].output_size();
+        comms.push(TranscriptRead::<F, E>::read_felts(&mut transcript, size)?);
+    }
+
+    let input_claims = verify_gkr_with_public_io(circuit, public_inputs, outputs, &mut transcript)?;
+    let accumulator = accumulate(circuit, private_inputs, &comms, input_claims);
+    let num_claims = accumulator.len();
+    accumulator.settle::<F, Pcs>(vp, &mut transcript)?;
+    Ok(num_claims)
+}
+
+// Collects the claims on private inputs with their commitments, in the order of
+// `private_inputs`.
+fn accumulate(
+    circuit: &Circuit<F, E>,
+    private_inputs: &[usize],
+    comms: &[Vec<F>],
+    input_claims: Vec<Vec<EvalClaim<E>>>,
+) -> OpeningAccumulator<Vec<F>, E> {
+    let mut accumulator = OpeningAccumulator::new();
+    for (idx, claims) in izip!(circuit.inputs(), input_claims) {
+        let Some(idx) = private_inputs.iter().position(|input| *input == idx) else {
+            continue;
+        };
+        for claim in claims {
+            accumulator.push(comms[idx].clone(), claim.point().to_vec(), claim.value());
+        }
+    }
+    accumulator
+}
+
+fn exit(msg: impl ToString) -> ! {
+    eprintln!("{}", msg.to_string());
+    process::exit(1)
+}
@@ -0,0 +1,419 @@
+use gkr::{
+    circuit::{
+        connect,
+        node::{InputNode, LogUpNode, Node, VanillaGate, VanillaNode},
+        Circuit, NodeId,
+    },
+    config::ProverConfig,
+    util::{
+        arithmetic::{div_ceil, powers, ExtensionField, PrimeField},
+        chain, izip, Itertools,
+    },
+    Error,
+};
+use rayon::{current_num_threads, prelude::*};
+use std::{iter, mem::size_of, num::NonZeroUsize};
+
+/// Maximum bits of a limb, which bounds the `2^limb_bits` table and the
+/// multiplicity buckets [`BitDecomposition::assign`] allocates per thread.
+pub const MAX_LIMB_BITS: usize = 22;
+
+/// Decomposition of `2^log2_size` values into `num_limbs` limbs of
+/// `limb_bits` bits each, little-endian.
+#[derive(Clone, Copy, Debug)]
+pub struct BitDecomposition {
+    log2_size: usize,
+    limb_bits: usize,
+    num_limbs: usize,
+}
+
+/// Nodes allocated by [`BitDecomposition::configure`]. The output of `diff`
+/// is zero iff the limbs recompose `value`, and `log_up` succeeds iff every
+/// limb is in `[0, 2^limb_bits)`.
+#[derive(Clone, Debug)]
+pub struct Decomposed {
+    pub limbs: Vec<NodeId>,
+    pub m: NodeId,
+    pub t: NodeId,
+    pub diff: NodeId,
+    pub log_up: NodeId,
+}
+
+impl BitDecomposition {
+    /// Fails unless limbs are 1 to [`MAX_LIMB_BITS`] bits and together cover
+    /// at most the 64 bits of values passed to [`BitDecomposition::assign`].
+    pub fn new(log2_size: usize, limb_bits: usize, num_limbs: usize) -> Result<Self, Error> {
+        if !(1..=MAX_LIMB_BITS).contains(&limb_bits) {
+            return Err(Error::InvalidCircuit(format!(
+                "Limbs have {limb_bits} bits but expected 1 to {MAX_LIMB_BITS}"
+            )));
+        }
+        if num_limbs == 0 || limb_bits * num_limbs > 64 {
+            return Err(Error::InvalidCircuit(format!(
+                "{num_limbs} limbs of {limb_bits} bits don't cover 1 to 64 bits"
+            )));
+        }
+        Ok(Self {
+            log2_size,
+            limb_bits,
+            num_limbs,
+        })
+    }
+
+    /// Picks the limb width covering `num_bits` over `F` that minimizes the
+    /// table size plus the number of lookups, which dominates the LogUp
+    /// prover, among those [`BitDecomposition::configure`] accepts.
+    pub fn auto<F: PrimeField>(log2_size: usize, num_bits: usize) -> Result<Self, Error> {
+        (1..=MAX_LIMB_BITS)
+            .filter_map(|limb_bits| {
+                Self::new(log2_size, limb_bits, num_bits.div_ceil(limb_bits)).ok()
+            })
+            .filter(|decomposition| decomposition.check_field::<F>().is_ok())
+            .min_by_key(|decomposition| {
+                (1 << decomposition.limb_bits) + (decomposition.num_limbs << log2_size)
+            })
+            .ok_or_else(|| {
+                Error::InvalidCircuit(format!(
+                    "No decomposition of 2^{log2_size} values of {num_bits} bits over a {}-bit field",
+                    F::NUM_BITS
+                ))
+            })
+    }
+
+    pub const fn log2_size(&self) -> usize {
+        self.log2_size
+    }
+
+    pub const fn limb_bits(&self) -> usize {
+        self.limb_bits
+    }
+
+    pub const fn num_limbs(&self) -> usize {
+        self.num_limbs
+    }
+
+    pub fn configure<F: PrimeField, E: ExtensionField<F>>(
+        &self,
+        circuit: &mut Circuit<F, E>,
+        value: NodeId,
+    ) -> Result<Decomposed, Error> {
+        if circuit.node(value).log2_output_size() != self.log2_size {
+            return Err(Error::InvalidCircuit(format!(
+                "Value has log2 size {} but decomposition expects {}",
+                circuit.node(value).log2_output_size(),
+                self.log2_size
+            )));
+        }
+        self.check_field::<F>()?;
+
+        let limbs = iter::repeat_with(|| circuit.insert(InputNode::new(self.log2_size, 1)))
+            .take(self.num_limbs)
+            .collect_vec();
+        let diff = {
+            let d_1 = chain![
+                [(None, (0, 0))],
+                izip!(powers(F::from(1 << self.limb_bits)), 1..)
+                    .map(|(scalar, idx)| (Some(-scalar), (idx, 0)))
+                    .take(self.num_limbs)
+            ]
+            .collect();
+            let gate = VanillaGate::new(None, d_1, Vec::new());
+            circuit.insert(VanillaNode::new(
+                self.num_limbs + 1,
+                0,
+                vec![gate],
+                1 << self.log2_size,
+            ))
+        };
+        for input in chain![[value], limbs.iter().copied()] {
+            connect!(circuit { diff <- input });
+        }
+
+        let m = circuit.insert(InputNode::new(self.limb_bits, 1));
+        let t = circuit.insert(InputNode::new(self.limb_bits, 1));
+        let log_up = circuit.insert(LogUpNode::new(
+            self.limb_bits,
+            self.log2_size,
+            self.num_limbs,
+        ));
+        for input in chain![[m, t], limbs.iter().copied()] {
+            connect!(circuit { log_up <- input });
+        }
+
+        Ok(Decomposed {
+            limbs,
+            m,
+            t,
+            diff,
+            log_up,
+        })
+    }
+
+    /// Rough estimate in bytes of the working memory of
+    /// [`BitDecomposition::assign`] over `num_threads` threads: the
+    /// `2^limb_bits` multiplicity buckets of each thread, plus the limbs,
+    /// multiplicities and table it returns.
+    pub fn estimate_memory<F: PrimeField>(&self, num_threads: usize) -> usize {
+        let table_size = 1 << self.limb_bits;
+        num_threads * table_size * size_of::<u64>()
+            + ((self.num_limbs << self.log2_size) + 2 * table_size) * size_of::<F>()
+    }
+
+    /// Returns [`Error::ExceededMemoryBudget`] if [`Self::estimate_memory`]
+    /// with the threads of `config` exceeds its memory budget, to be checked
+    /// before [`BitDecomposition::assign`].
+    pub fn check_memory_budget<F: PrimeField>(&self, config: &ProverConfig) -> Result<(), Error> {
+        let Some(memory_budget) = config.memory_budget() else {
+            return Ok(());
+        };
+        let num_threads = config
+            .num_threads()
+            .map_or_else(current_num_threads, NonZeroUsize::get);
+        let estimate = self.estimate_memory::<F>(num_threads);
+        if estimate > memory_budget {
+            return Err(Error::ExceededMemoryBudget(format!(
+                "Estimated {estimate} bytes but budget is {memory_budget}"
+            )));
+        }
+        Ok(())
+    }
+
+    // Checks the soundness bounds that depend on the field.
+    fn check_field<F: PrimeField>(&self) -> Result<(), Error> {
+        // Limbs have to cover less than the modulus, otherwise `value` and
+        // `value + p` might both recompose from in-range limbs.
+        if self.limb_bits * self.num_limbs >= F::NUM_BITS as usize {
+            return Err(Error::InvalidCircuit(format!(
+                "Limbs cover {} bits but field has {}",
+                self.limb_bits * self.num_limbs,
+                F::NUM_BITS
+            )));
+        }
+        // Multiplicities are at most the number of lookups, which has to stay
+        // below the characteristic for LogUp to be sound.
+        let log2_num_lookups = self.num_limbs.next_power_of_two().ilog2() as usize + self.log2_size;
+        if log2_num_lookups >= F::NUM_BITS as usize - 1 {
+            return Err(Error::InvalidCircuit(format!(
+                "2^{log2_num_lookups} lookups might overflow multiplicities in a {}-bit field",
+                F::NUM_BITS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the limbs, multiplicities and table for `values`, in the order
+    /// of [`Decomposed`], or [`Error::InvalidWitness`] if there aren't
+    /// `2^log2_size` values or some of them don't fit in the limbs.
+    #[allow(clippy::type_complexity)]
+    pub fn assign<F: PrimeField>(
+        &self,
+        values: &[u64],
+    ) -> Result<(Vec<Vec<F>>, Vec<F>, Vec<F>), Error> {
+        if values.len() != 1 << self.log2_size {
+            return Err(Error::InvalidWitness(format!(
+                "Expected {} values but got {}",
+                1 << self.log2_size,
+                values.len()
+            )));
+        }
+        let num_bits = self.limb_bits * self.num_limbs;
+        if num_bits < 64 {
+            if let Some(value) = values.iter().find(|value| **value >> num_bits != 0) {
+                return Err(Error::InvalidWitness(format!(
+                    "Value {value} doesn't fit in {num_bits} bits"
+                )));
+            }
+        }
+
+        let mask = (1 << self.limb_bits) - 1;
+        let limb = |value: u64, idx: usize| (value >> (idx * self.limb_bits)) & mask;
+        let limbs = (0..self.num_limbs)
+            .into_par_iter()
+            .map(|idx| {
+                values
+                    .par_iter()
+                    .map(|value| F::from(limb(*value, idx)))
+                    .collect()
+            })
+            .collect();
+        // Counts per thread into its own buckets, then merges them.
+        let chunk_size = div_ceil(values.len(), current_num_threads());
+        let m = values
+            .par_chunks(chunk_size)
+            .map(|values| {
+                let mut m = vec![0u64; 1 << self.limb_bits];
+                for value in values {
+                    (0..self.num_limbs).for_each(|idx| m[limb(*value, idx) as usize] += 1);
+                }
+                m
+            })
+            .reduce_with(|mut lhs, rhs| {
+                izip!(&mut lhs, rhs).for_each(|(lhs, rhs)| *lhs += rhs);
+                lhs
+            })
+            .unwrap();
+        let m = m.into_par_iter().map(F::from).collect();
+        let t = (0..1 << self.limb_bits).map(F::from).collect();
+        Ok((limbs, m, t))
+    }
+}
+
+/// Allocates an input of `2^log2_size` values and decomposes it into limbs of
+/// `limb_bits` bits covering `num_bits`.
+pub fn decompose<F: PrimeField, E: ExtensionField<F>>(
+    circuit: &mut Circuit<F, E>,
+    log2_size: usize,
+    num_bits: usize,
+    limb_bits: usize,
+) -> Result<(NodeId, BitDecomposition, Decomposed), Error> {
+    // `new` rejects zero `limb_bits`, `max` only avoids dividing by it first.
+    let num_limbs = num_bits.div_ceil(limb_bits.max(1));
+    let decomposition = BitDecomposition::new(log2_size, limb_bits, num_limbs)?;
+    let value = circuit.insert(InputNode::new(log2_size, 1));
+    let decomposed = decomposition.configure(circuit, value)?;
+    Ok((value, decomposition, decomposed))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::range::{decompose, BitDecomposition};
+    use gkr::{
+        circuit::{node::InputNode, Circuit},
+        config::ProverConfig,
+        dev::run_gkr_with_values,
+        ff_ext::{
+            babybear::{BabyBear, BabyBearExt4},
+            mersenne31::{Mersenne31, Mersenne31Ext4},
+        },
+        poly::box_dense_poly,
+        util::{
+            arithmetic::{ExtensionField, PrimeField},
+            chain,
+            dev::seeded_std_rng,
+            Itertools, RngCore,
+        },
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn auto() {
+        let limb_bits = |log2_size, num_bits| {
+            BitDecomposition::auto::<Goldilocks>(log2_size, num_bits)
+                .unwrap()
+                .limb_bits()
+        };
+        assert_eq!(limb_bits(6, 16), 6);
+        assert_eq!(limb_bits(10, 63), 9);
+        assert_eq!(limb_bits(20, 30), 15);
+        assert_eq!(limb_bits(20, 32), 16);
+        assert!(matches!(
+            BitDecomposition::auto::<Goldilocks>(6, 64),
+            Err(Error::InvalidCircuit(_))
+        ));
+
+        let decomposition = BitDecomposition::auto::<BabyBear>(28, 30).unwrap();
+        assert_eq!(decomposition.num_limbs(), 2);
+        let mut circuit = Circuit::<BabyBear, BabyBearExt4>::default();
+        let value = circuit.insert(InputNode::new(28, 1));
+        assert!(decomposition.configure(&mut circuit, value).is_ok());
+
+        // Two 15-bit limbs would overflow multiplicities, and one 30-bit limb
+        // exceeds `MAX_LIMB_BITS`.
+        assert!(matches!(
+            BitDecomposition::auto::<BabyBear>(29, 30),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn bit_decomposition() {
+        run_bit_decomposition::<Goldilocks, GoldilocksExt2>(&[(16, 4), (20, 8), (63, 9)]);
+        run_bit_decomposition::<BabyBear, BabyBearExt4>(&[(16, 4), (30, 10)]);
+        run_bit_decomposition::<Mersenne31, Mersenne31Ext4>(&[(16, 4), (30, 10)]);
+    }
+
+    #[test]
+    fn invalid_limbs() {
+        let mut circuit = Circuit::<Goldilocks, GoldilocksExt2>::default();
+        for (num_bits, limb_bits) in [(62, 13), (16, 0), (32, 32), (23, 23), (0, 8)] {
+            assert!(matches!(
+                decompose(&mut circuit, 6, num_bits, limb_bits),
+                Err(Error::InvalidCircuit(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_values() {
+        let decomposition = BitDecomposition::new(2, 4, 2).unwrap();
+        assert!(decomposition.assign::<Goldilocks>(&[0, 1, 2, 255]).is_ok());
+        for values in [&[0, 1, 2][..], &[0, 1, 2, 256]] {
+            assert!(matches!(
+                decomposition.assign::<Goldilocks>(values),
+                Err(Error::InvalidWitness(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn memory_budget() {
+        let decomposition = BitDecomposition::new(10, 20, 2).unwrap();
+        let estimate = decomposition.estimate_memory::<Goldilocks>(4);
+        assert!(estimate > 4 << 20 << 3);
+        assert!(estimate > decomposition.estimate_memory::<Goldilocks>(1));
+
+        let config = ProverConfig::new().with_num_threads(NonZeroUsize::new(4).unwrap());
+        for (memory_budget, ok) in [(estimate, true), (estimate - 1, false)] {
+            let config = config.clone().with_memory_budget(memory_budget);
+            assert_eq!(
+                decomposition
+                    .check_memory_budget::<Goldilocks>(&config)
+                    .is_ok(),
+                ok
+            );
+        }
+        assert!(decomposition
+            .check_memory_budget::<Goldilocks>(&config)
+            .is_ok());
+    }
+
+    #[test]
+    fn multiplicity_overflow() {
+        let mut circuit = Circuit::<BabyBear, BabyBearExt4>::default();
+        assert!(matches!(
+            decompose(&mut circuit, 29, 30, 10),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+
+    fn run_bit_decomposition<F: PrimeField, E: ExtensionField<F>>(cases: &[(usize, usize)]) {
+        let mut rng = seeded_std_rng();
+        let log2_size = 6;
+        for (num_bits, limb_bits) in cases.iter().copied() {
+            let mut circuit = Circuit::<F, E>::default();
+            let (_, decomposition, decomposed) =
+                decompose(&mut circuit, log2_size, num_bits, limb_bits).unwrap();
+            assert_eq!(decomposed.limbs.len(), num_bits.div_ceil(limb_bits));
+
+            let values = (0..1 << log2_size)
+                .map(|_| rng.next_u64() >> (64 - num_bits))
+                .collect_vec();
+            let (limbs, m, t) = decomposition.assign::<F>(&values).unwrap();
+            let inputs = chain![
+                [values.iter().copied().map(F::from).collect_vec()],
+                limbs,
+                [m, t]
+            ]
+            .map(box_dense_poly)
+            .collect_vec();
+            let values = circuit.evaluate(inputs);
+            assert_eq!(circuit.nodes().len(), values.len());
+            assert!(circuit
+                .outputs()
+                .all(|idx| { (0..values[idx].len()).all(|b| values[idx][b].is_zero_vartime()) }));
+            run_gkr_with_values(&circuit, &values, &mut rng);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use criterion::{
     BenchmarkId, Criterion,
 };
 use gkr::{
-    poly::{box_dense_poly, eq_poly, DensePolynomial, MultilinearPoly},
+    poly::{box_dense_poly, eq_poly, DensePolynomial, MultilinearPoly, PolyExpr},
     sum_check::{quadratic::Quadratic, SumCheckFunction, SumCheckPoly},
     util::{
         arithmetic::Field,
@@ -11,7 +11,7 @@ use gkr::{
         RngCore,
     },
 };
-use goldilocks::GoldilocksExt2;
+use goldilocks::{Goldilocks, GoldilocksExt2};
 use halo2_curves::bn256;
 use std::{array, ops::Range};
 
@@ -82,10 +82,33 @@ fn bench_compute_sum_qudratic(c: &mut Criterion) {
     }
 }
 
+fn bench_poly_expr_evaluate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("poly_expr_evaluate");
+
+    let mut rng = seeded_std_rng();
+    for num_terms in [4, 16, 64, 256, 1024] {
+        let expr = PolyExpr::<Goldilocks>::Sum(
+            (0..num_terms)
+                .map(|i| PolyExpr::mul(PolyExpr::u64(1 << (i % 64)), PolyExpr::Var(i)))
+                .collect(),
+        );
+        let x = rand_vec::<GoldilocksExt2>(num_terms, &mut rng);
+        group.bench_with_input(BenchmarkId::new("evaluate", num_terms), &x, |b, x| {
+            b.iter(|| black_box(&expr).evaluate(black_box(x)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("evaluate_serial", num_terms),
+            &x,
+            |b, x| b.iter(|| black_box(&expr).evaluate_serial(black_box(x))),
+        );
+    }
+}
+
 criterion_group!(
     benches,
     bench_eq_poly,
     bench_fix_var,
-    bench_compute_sum_qudratic
+    bench_compute_sum_qudratic,
+    bench_poly_expr_evaluate
 );
 criterion_main!(benches);
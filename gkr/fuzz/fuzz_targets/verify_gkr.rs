@@ -0,0 +1,39 @@
+//! Checks that `verify_gkr` rejects a mutated grand product proof without
+//! panicking, with the [`Mutation`] decoded from the fuzzer input.
+//!
+//! ```text
+//! cargo +nightly fuzz run verify_gkr
+//! ```
+
+#![no_main]
+
+use gkr::{
+    circuit::node::EvalClaim,
+    dev::{grand_product_circuit, verify_mutated, Mutation},
+    ff_ext::goldilocks::{Goldilocks, GoldilocksExt2},
+    poly::MultilinearPoly,
+    prove_gkr,
+    transcript::StdRngTranscript,
+    util::dev::seeded_std_rng,
+};
+use libfuzzer_sys::fuzz_target;
+
+const LOG2_INPUT_SIZE: usize = 4;
+
+fuzz_target!(|data: &[u8]| {
+    let Some(mutation) = Mutation::from_bytes(data) else {
+        return;
+    };
+
+    let (circuit, values) =
+        grand_product_circuit::<Goldilocks, GoldilocksExt2>(LOG2_INPUT_SIZE, &mut seeded_std_rng());
+    let output_claims = vec![EvalClaim::new(vec![], values.last().unwrap().evaluate(&[]))];
+
+    let proof = {
+        let mut transcript = StdRngTranscript::default();
+        prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+        transcript.into_proof()
+    };
+
+    verify_mutated(&circuit, &values, &output_claims, &proof, mutation).unwrap();
+});
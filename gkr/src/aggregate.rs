@@ -0,0 +1,199 @@
+use crate::{
+    absorb_statement, check_input_comms,
+    circuit::{node::EvalClaim, Circuit},
+    pcs::{MultilinearPcs, OpeningAccumulator},
+    poly::{BoxMultilinearPoly, DynMultilinearPoly},
+    prove_claims,
+    transcript::{Transcript, TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{ExtensionField, Field},
+        izip_eq, Itertools,
+    },
+    verify_claims, Error,
+};
+
+/// Statement of one proof in an aggregate, with the commitments to the inputs
+/// of `circuit` in [`Circuit::inputs`] order.
+#[derive(Clone, Debug)]
+pub struct GkrInstance<'a, F, E, C> {
+    circuit: &'a Circuit<F, E>,
+    output_claims: Vec<EvalClaim<E>>,
+    input_comms: Vec<C>,
+}
+
+impl<'a, F: Field, E: ExtensionField<F>, C> GkrInstance<'a, F, E, C> {
+    /// Returns [`Error::InvalidCircuit`] unless there is one commitment per
+    /// input of `circuit`.
+    pub fn new(
+        circuit: &'a Circuit<F, E>,
+        output_claims: Vec<EvalClaim<E>>,
+        input_comms: Vec<C>,
+    ) -> Result<Self, Error> {
+        check_input_comms(circuit, &input_comms)?;
+        Ok(Self {
+            circuit,
+            output_claims,
+            input_comms,
+        })
+    }
+
+    pub fn circuit(&self) -> &Circuit<F, E> {
+        self.circuit
+    }
+
+    pub fn output_claims(&self) -> &[EvalClaim<E>] {
+        &self.output_claims
+    }
+
+    pub fn input_comms(&self) -> &[C] {
+        &self.input_comms
+    }
+}
+
+/// Absorbs the statements of all `instances` with [`absorb_statement`] before
+/// any challenge is squeezed, so the LogUp `gamma` and every later challenge
+/// are bound to them. Both [`prove_gkr_aggregated`] and
+/// [`verify_gkr_aggregated`] start with it and absorb nothing else.
+pub fn absorb_instances<F, E, Pcs>(
+    instances: &[GkrInstance<F, E, Pcs::Commitment>],
+    transcript: &mut impl Transcript<F, E>,
+) where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    for instance in instances {
+        absorb_statement::<F, E, Pcs>(&instance.input_comms, &instance.output_claims, transcript);
+    }
+}
+
+/// Proves each of `instances` with `values[i]` in turn on `transcript`, then
+/// opens the claims on all committed inputs with a single
+/// [`MultilinearPcs::batch_open`].
+pub fn prove_gkr_aggregated<F, E, Pcs>(
+    pp: &Pcs::ProverParam,
+    instances: &[GkrInstance<F, E, Pcs::Commitment>],
+    values: &[&[BoxMultilinearPoly<F, E>]],
+    transcript: &mut impl TranscriptWrite<F, E>,
+) -> Result<(), Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    absorb_instances::<F, E, Pcs>(instances, transcript);
+
+    let mut accumulator = OpeningAccumulator::new();
+    let mut polys = Vec::<(&Pcs::Commitment, &DynMultilinearPoly<F, E>)>::new();
+    for (instance, values) in izip_eq!(instances, values) {
+        let circuit = instance.circuit;
+        let input_claims = prove_claims(
+            circuit,
+            values,
+            &instance.output_claims,
+            transcript,
+            |_, _| {},
+        )?;
+        for (idx, comm) in izip_eq!(circuit.inputs(), &instance.input_comms) {
+            polys.push((comm, &*values[idx]));
+        }
+        accumulator.push_input_claims(&instance.input_comms, input_claims)?;
+    }
+    let poly = |comm: &Pcs::Commitment| Some(polys.iter().find(|(c, _)| *c == comm)?.1);
+    accumulator.open::<F, Pcs>(pp, poly, transcript)
+}
+
+/// Verifies a proof made by [`prove_gkr_aggregated`], accumulating the claims
+/// on inputs of all instances and settling them once at the end.
+pub fn verify_gkr_aggregated<F, E, Pcs>(
+    vp: &Pcs::VerifierParam,
+    instances: &[GkrInstance<F, E, Pcs::Commitment>],
+    transcript: &mut impl TranscriptRead<F, E>,
+) -> Result<(), Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    absorb_instances::<F, E, Pcs>(instances, transcript);
+
+    let mut accumulator = OpeningAccumulator::new();
+    for instance in instances {
+        let input_claims = verify_claims(instance.circuit, &instance.output_claims, transcript)?;
+        accumulator.push_input_claims(&instance.input_comms, input_claims)?;
+    }
+    accumulator.settle::<F, Pcs>(vp, transcript)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        aggregate::{absorb_instances, prove_gkr_aggregated, verify_gkr_aggregated, GkrInstance},
+        circuit::node::EvalClaim,
+        dev::grand_product_circuit,
+        pcs::{MultilinearPcs, TrivialPcs},
+        poly::MultilinearPoly,
+        transcript::{Keccak256Transcript, Transcript},
+        util::{arithmetic::Field, dev::seeded_std_rng, Itertools},
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    type Pcs = TrivialPcs<Goldilocks, GoldilocksExt2>;
+
+    #[test]
+    fn aggregate() {
+        let mut rng = seeded_std_rng();
+        let (pp, vp) = Pcs::trim(&Pcs::setup(4, &mut rng).unwrap(), 4).unwrap();
+        let circuits =
+            [3, 4].map(|log2_input_size| grand_product_circuit(log2_input_size, &mut rng));
+        let instances = circuits
+            .iter()
+            .map(|(circuit, values)| {
+                let output_claims =
+                    vec![EvalClaim::new(vec![], values.last().unwrap().evaluate(&[]))];
+                let input_comms = vec![Pcs::commit(&pp, &*values[0]).unwrap()];
+                GkrInstance::new(circuit, output_claims, input_comms).unwrap()
+            })
+            .collect_vec();
+
+        let proof = {
+            let values = circuits
+                .iter()
+                .map(|(_, values)| values.as_slice())
+                .collect_vec();
+            let mut transcript = Keccak256Transcript::new(Vec::new());
+            prove_gkr_aggregated::<_, _, Pcs>(&pp, &instances, &values, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+
+        let mut transcript = Keccak256Transcript::from_proof(&proof);
+        verify_gkr_aggregated::<_, _, Pcs>(&vp, &instances, &mut transcript).unwrap();
+
+        let squeeze = |instances: &[GkrInstance<Goldilocks, GoldilocksExt2, _>]| {
+            let mut transcript = Keccak256Transcript::new(Vec::<u8>::new());
+            absorb_instances::<_, _, Pcs>(instances, &mut transcript);
+            Transcript::<Goldilocks, GoldilocksExt2>::squeeze_challenge(&mut transcript)
+        };
+        let mut other_claims = instances.clone();
+        other_claims[0].output_claims[0] = EvalClaim::new(vec![], GoldilocksExt2::ONE);
+        assert_ne!(squeeze(&instances), squeeze(&other_claims));
+
+        // Commitments are absorbed first, so a tampered one already changes
+        // the challenges of the GKR proof of the first instance.
+        let mut instances = instances;
+        instances[1].input_comms[0][0] += Goldilocks::ONE;
+        let mut transcript = Keccak256Transcript::from_proof(&proof);
+        assert!(matches!(
+            verify_gkr_aggregated::<_, _, Pcs>(&vp, &instances, &mut transcript),
+            Err(Error::InvalidSumCheck(_))
+        ));
+
+        let (circuit, values) = &circuits[0];
+        let output_claims = vec![EvalClaim::new(vec![], values.last().unwrap().evaluate(&[]))];
+        assert!(matches!(
+            GkrInstance::<_, _, Vec<Goldilocks>>::new(circuit, output_claims, vec![]),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+}
@@ -6,15 +6,22 @@ use crate::{
     poly::BoxMultilinearPoly,
     util::{
         arithmetic::{ExtensionField, Field},
-        izip_eq, Itertools,
+        chain, izip_eq, Itertools,
     },
 };
+use rayon::prelude::*;
 use std::{iter, ops::Deref};
 
+mod builder;
 mod dag;
+mod description;
 pub mod node;
+mod witness;
 
+pub use builder::{CircuitBuilder, Wire};
 pub use dag::NodeId;
+pub use description::{CircuitDescription, NodeDescription};
+pub use witness::WitnessGenerator;
 
 #[derive(Debug, Default)]
 pub struct Circuit<F, E> {
@@ -40,6 +47,9 @@ impl<F: Field, E: ExtensionField<F>> Circuit<F, E> {
         self.topo = self.dag.topo();
     }
 
+    /// Evaluates all nodes level by level, where nodes in the same level are
+    /// independent and evaluated concurrently on the current rayon pool (use
+    /// `ThreadPool::install` to bound the number of threads).
     pub fn evaluate<'a>(
         &self,
         inputs: Vec<BoxMultilinearPoly<'a, F, E>>,
@@ -47,16 +57,76 @@ impl<F: Field, E: ExtensionField<F>> Circuit<F, E> {
         let mut values = Vec::from_iter(iter::repeat_with(|| None).take(self.nodes().len()));
 
         izip_eq!(self.inputs(), inputs).for_each(|(idx, input)| values[idx] = input.into());
-        self.topo_iter()
-            .filter(|(_, node)| !node.is_input())
-            .for_each(|(idx, node)| {
-                let inputs = self.predec(idx).map(|i| values[i].as_ref().unwrap());
-                values[idx] = node.evaluate(inputs.collect()).into()
-            });
+        for level in self.levels() {
+            let level = level
+                .into_iter()
+                .filter(|idx| !self.nodes()[*idx].is_input())
+                .collect_vec();
+            let outputs = level
+                .par_iter()
+                .map(|idx| {
+                    let inputs = self.predec(*idx).map(|i| values[i].as_ref().unwrap());
+                    self.nodes()[*idx].evaluate(inputs.collect())
+                })
+                .collect::<Vec<_>>();
+            izip_eq!(level, outputs).for_each(|(idx, output)| values[idx] = output.into());
+        }
 
         values.into_iter().map(Option::unwrap).collect()
     }
 
+    /// Returns nodes grouped by depth, where the depth of a node is the length
+    /// of the longest path from an input or constant to it.
+    pub fn levels(&self) -> Vec<Vec<usize>> {
+        let mut depths = vec![0; self.nodes().len()];
+        let mut levels = Vec::<Vec<usize>>::new();
+        for (idx, _) in self.topo_iter() {
+            depths[idx] = self.predec(idx).map(|i| depths[i] + 1).max().unwrap_or(0);
+            if levels.len() <= depths[idx] {
+                levels.resize_with(depths[idx] + 1, Vec::new);
+            }
+            levels[depths[idx]].push(idx);
+        }
+        levels.iter_mut().for_each(|level| level.sort_unstable());
+        levels
+    }
+
+    /// Returns the node graph in graphviz DOT format, with edges labeled by
+    /// the position of the input they feed.
+    pub fn to_dot(&self) -> String {
+        let nodes = self.nodes().iter().enumerate().map(|(idx, node)| {
+            let shape = if node.is_input() { "box" } else { "ellipse" };
+            format!(
+                "    {idx} [shape={shape}, label=\"{idx}: {}\\nlog2_input_size: {}\\nlog2_output_size: {}\\nnum_inputs: {}\"];\n",
+                node.name(),
+                node.log2_input_size(),
+                node.log2_output_size(),
+                self.predec(idx).count(),
+            )
+        });
+        let edges = (0..self.nodes().len()).flat_map(|to| {
+            self.predec(to)
+                .enumerate()
+                .map(move |(pos, from)| format!("    {from} -> {to} [label=\"{pos}\"];\n"))
+        });
+        chain![
+            ["digraph circuit {\n".to_string()],
+            nodes,
+            edges,
+            ["}\n".to_string()],
+        ]
+        .collect()
+    }
+
+    /// Returns input nodes, which unlike [`ConstantNode`](node::ConstantNode)s
+    /// are the nodes without predecessors whose values are provided by the
+    /// prover.
+    pub fn inputs(&self) -> impl Iterator<Item = usize> + '_ {
+        self.dag
+            .inputs()
+            .filter(|idx| self.nodes()[*idx].is_input())
+    }
+
     pub(crate) fn topo_iter(&self) -> impl DoubleEndedIterator<Item = (usize, &dyn Node<F, E>)> {
         self.topo.iter().map(|idx| (*idx, &*self.nodes()[*idx]))
     }
@@ -85,15 +155,24 @@ pub use connect;
 #[cfg(test)]
 pub(super) mod test {
     use crate::{
-        circuit::Circuit,
-        dev::run_gkr_with_values,
-        poly::BoxMultilinearPoly,
+        circuit::{
+            node::{VanillaGate, VanillaNode},
+            Circuit, CircuitBuilder,
+        },
+        dev::{grand_product_circuit, run_gkr_with_values},
+        ff_ext::babybear::{BabyBear, BabyBearExt4},
+        poly::{box_dense_poly, BoxMultilinearPoly},
         util::{
             arithmetic::{ExtensionField, PrimeField},
-            dev::{assert_polys_eq, seeded_std_rng},
+            chain,
+            dev::{assert_polys_eq, rand_vec, seeded_std_rng},
+            izip_eq, Itertools,
         },
     };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+    use halo2_curves::bn256;
     use rand::rngs::StdRng;
+    use std::iter;
 
     pub(super) type TestData<F, E> = (
         Circuit<F, E>,
@@ -101,6 +180,129 @@ pub(super) mod test {
         Option<Vec<BoxMultilinearPoly<'static, F, E>>>,
     );
 
+    #[test]
+    fn levels() {
+        let mut rng = seeded_std_rng();
+        let hadamard = || VanillaNode::new(2, 0, vec![VanillaGate::mul((0, 0), (1, 0))], 1 << 4);
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [a, b, c, d] = [(); 4].map(|_| builder.input(4));
+        let ab = builder.vanilla(hadamard(), &[a, b]).unwrap();
+        let cd = builder.vanilla(hadamard(), &[c, d]).unwrap();
+        let abcd = builder.vanilla(hadamard(), &[ab, cd]).unwrap();
+        builder.output(abcd);
+        let circuit = builder.build().unwrap();
+        assert_eq!(circuit.levels(), [vec![0, 1, 2, 3], vec![4, 5], vec![6]]);
+
+        let inputs = Vec::from_iter(iter::repeat_with(|| rand_vec(1 << 4, &mut rng)).take(4));
+        let hadamard = |lhs: &[_], rhs: &[_]| izip_eq!(lhs, rhs).map(|(l, r)| *l * r).collect_vec();
+        let ab = hadamard(&inputs[0], &inputs[1]);
+        let cd = hadamard(&inputs[2], &inputs[3]);
+        let abcd = hadamard(&ab, &cd);
+        let expected = chain![inputs.clone(), [ab, cd, abcd]]
+            .map(box_dense_poly)
+            .collect_vec();
+        let values = circuit.evaluate(inputs.into_iter().map(box_dense_poly).collect());
+        assert_polys_eq(&values, &expected);
+    }
+
+    #[test]
+    fn fan_out() {
+        let mut rng = seeded_std_rng();
+        let node = |gate| VanillaNode::new(2, 0, vec![gate], 1 << 4);
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [x, y] = [(); 2].map(|_| builder.input(4));
+        let xy = builder
+            .vanilla(node(VanillaGate::mul((0, 0), (1, 0))), &[x, y])
+            .unwrap();
+        let x_plus_y = builder
+            .vanilla(node(VanillaGate::add((0, 0), (1, 0))), &[x, y])
+            .unwrap();
+        let outputs = [
+            VanillaGate::mul((0, 0), (1, 0)),
+            VanillaGate::sub((0, 0), (1, 0)),
+        ]
+        .map(|gate| builder.vanilla(node(gate), &[xy, x_plus_y]).unwrap());
+        outputs
+            .into_iter()
+            .for_each(|output| builder.output(output));
+        let circuit = builder.build().unwrap();
+        assert_eq!(circuit.succ(xy.id().0).count(), 2);
+
+        let inputs = [(); 2].map(|_| box_dense_poly(rand_vec(1 << 4, &mut rng)));
+        let values = circuit.evaluate(inputs.into());
+        run_gkr_with_values(&circuit, &values, &mut rng);
+    }
+
+    #[test]
+    fn to_dot() {
+        let (circuit, _) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(2, &mut seeded_std_rng());
+        assert_eq!(
+            circuit.to_dot(),
+            [
+                "digraph circuit {",
+                "    0 [shape=box, label=\"0: InputNode\\nlog2_input_size: 2\\nlog2_output_size: 2\\nnum_inputs: 0\"];",
+                "    1 [shape=ellipse, label=\"1: VanillaNode\\nlog2_input_size: 2\\nlog2_output_size: 1\\nnum_inputs: 1\"];",
+                "    2 [shape=ellipse, label=\"2: VanillaNode\\nlog2_input_size: 1\\nlog2_output_size: 0\\nnum_inputs: 1\"];",
+                "    0 -> 1 [label=\"0\"];",
+                "    1 -> 2 [label=\"0\"];",
+                "}\n",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn cross_field_outputs() {
+        // Inputs are small enough for the product not to wrap in any field.
+        fn output<F: PrimeField, E: ExtensionField<F>>(log2_input_size: usize) -> u64 {
+            let (circuit, _) =
+                grand_product_circuit::<F, E>(log2_input_size, &mut seeded_std_rng());
+            let input = (0..1 << log2_input_size)
+                .map(|i| F::from(i % 3 + 1))
+                .collect_vec();
+            let values = circuit.evaluate(vec![box_dense_poly(input)]);
+            run_gkr_with_values(&circuit, &values, seeded_std_rng());
+
+            let repr = values.last().unwrap()[0].to_repr();
+            let (lo, hi) = repr.as_ref().split_at(8);
+            assert!(hi.iter().all(|byte| *byte == 0));
+            u64::from_le_bytes(lo.try_into().unwrap())
+        }
+
+        let log2_input_size = 4;
+        let expected = (0..1 << log2_input_size)
+            .map(|i| i % 3 + 1)
+            .product::<u64>();
+        assert_eq!(
+            output::<Goldilocks, GoldilocksExt2>(log2_input_size),
+            expected
+        );
+        assert_eq!(output::<bn256::Fr, bn256::Fr>(log2_input_size), expected);
+        assert_eq!(output::<BabyBear, BabyBearExt4>(log2_input_size), expected);
+    }
+
+    // Runs `run_circuit` with `$f` over each field the crate is tested with, to
+    // catch assumptions on the field size.
+    macro_rules! run_circuit_over_fields {
+        ($f:expr) => {{
+            use crate::{
+                circuit::test::run_circuit,
+                ff_ext::babybear::{BabyBear, BabyBearExt4},
+            };
+            use goldilocks::{Goldilocks, GoldilocksExt2};
+            use halo2_curves::bn256;
+
+            run_circuit::<Goldilocks, GoldilocksExt2>($f);
+            run_circuit::<bn256::Fr, bn256::Fr>($f);
+            run_circuit::<BabyBear, BabyBearExt4>($f);
+        }};
+    }
+
+    pub(super) use run_circuit_over_fields;
+
     pub(super) fn run_circuit<F: PrimeField, E: ExtensionField<F>>(
         f: impl Fn(usize, &mut StdRng) -> TestData<F, E>,
     ) {
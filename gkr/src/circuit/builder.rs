@@ -0,0 +1,246 @@
+use crate::{
+    circuit::{
+        node::{ConstantNode, InputNode, LogUpNode, Node, VanillaNode},
+        Circuit, NodeId,
+    },
+    util::{
+        arithmetic::{ExtensionField, Field},
+        izip, Itertools,
+    },
+    Error,
+};
+use std::collections::BTreeSet;
+
+/// Handle to the output of a node added to a [`CircuitBuilder`].
+#[derive(Clone, Copy, Debug)]
+pub struct Wire {
+    id: NodeId,
+    log2_size: usize,
+}
+
+impl Wire {
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn log2_size(&self) -> usize {
+        self.log2_size
+    }
+}
+
+/// Builds a [`Circuit`] from wires, validating sizes and arities as nodes are
+/// added instead of panicking in [`Circuit::connect`].
+#[derive(Debug)]
+pub struct CircuitBuilder<F, E> {
+    circuit: Circuit<F, E>,
+    outputs: BTreeSet<usize>,
+}
+
+impl<F: Field, E: ExtensionField<F>> Default for CircuitBuilder<F, E> {
+    fn default() -> Self {
+        Self {
+            circuit: Circuit::default(),
+            outputs: BTreeSet::new(),
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> CircuitBuilder<F, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input(&mut self, log2_size: usize) -> Wire {
+        let id = self.circuit.insert(InputNode::new(log2_size, 1));
+        Wire { id, log2_size }
+    }
+
+    pub fn constant(&mut self, node: ConstantNode<F, E>) -> Wire {
+        let log2_size = Node::<F, E>::log2_output_size(&node);
+        let id = self.circuit.insert(node);
+        Wire { id, log2_size }
+    }
+
+    pub fn node(
+        &mut self,
+        node: impl Node<F, E> + 'static,
+        inputs: &[Wire],
+    ) -> Result<Wire, Error> {
+        if node.is_input() {
+            return Err(err_invalid_circuit("Input nodes must be added by input"));
+        }
+        if inputs.is_empty() {
+            return Err(err_invalid_circuit(
+                "Node without inputs, use constant instead",
+            ));
+        }
+        if inputs.len() != node.input_arity() {
+            return Err(err_invalid_circuit(format!(
+                "Expected {} inputs but got {}",
+                node.input_arity(),
+                inputs.len()
+            )));
+        }
+        if let Some(input) = inputs
+            .iter()
+            .find(|input| input.log2_size > node.log2_input_size())
+        {
+            return Err(err_invalid_circuit(format!(
+                "Input {:?} of log2 size {} exceeds node log2 input size {}",
+                input.id,
+                input.log2_size,
+                node.log2_input_size()
+            )));
+        }
+        if let Some((idx, (input, log2_size))) = node.log2_input_sizes().and_then(|log2_sizes| {
+            izip!(inputs, log2_sizes)
+                .find_position(|(input, log2_size)| input.log2_size != *log2_size)
+        }) {
+            return Err(err_invalid_circuit(format!(
+                "Input {idx} of log2 size {} mismatches node log2 input size {log2_size}",
+                input.log2_size
+            )));
+        }
+        if inputs
+            .iter()
+            .enumerate()
+            .any(|(i, input)| inputs[..i].iter().any(|prev| prev.id.0 == input.id.0))
+        {
+            return Err(err_invalid_circuit("Same wire connected twice"));
+        }
+
+        let log2_size = node.log2_output_size();
+        let id = self.circuit.insert(node);
+        inputs
+            .iter()
+            .for_each(|input| self.circuit.connect(input.id, id));
+        Ok(Wire { id, log2_size })
+    }
+
+    pub fn vanilla(&mut self, node: VanillaNode<F, E>, inputs: &[Wire]) -> Result<Wire, Error> {
+        self.node(node, inputs)
+    }
+
+    /// Adds a [`LogUpNode`] proving every entry of `fs` is in table `t` with
+    /// multiplicities `m`, inferring the node sizes from the wires.
+    pub fn log_up(&mut self, m: Wire, t: Wire, fs: &[Wire]) -> Result<Wire, Error> {
+        if m.log2_size != t.log2_size {
+            return Err(err_invalid_circuit(
+                "Multiplicities and table sizes mismatch",
+            ));
+        }
+        let Some(log2_f_size) = fs.first().map(Wire::log2_size) else {
+            return Err(err_invalid_circuit("Lookup without inputs"));
+        };
+        if fs.iter().any(|f| f.log2_size != log2_f_size) {
+            return Err(err_invalid_circuit("Lookup inputs sizes mismatch"));
+        }
+
+        let node = LogUpNode::new(t.log2_size, log2_f_size, fs.len());
+        let inputs = [m, t]
+            .into_iter()
+            .chain(fs.iter().copied())
+            .collect::<Vec<_>>();
+        self.node(node, &inputs)
+    }
+
+    pub fn output(&mut self, wire: Wire) {
+        self.outputs.insert(wire.id.0);
+    }
+
+    /// Returns the circuit, checking the nodes without successors are exactly
+    /// the declared outputs.
+    pub fn build(self) -> Result<Circuit<F, E>, Error> {
+        let outputs = self.circuit.outputs().collect::<BTreeSet<_>>();
+        if let Some(idx) = outputs.symmetric_difference(&self.outputs).next() {
+            return Err(err_invalid_circuit(if outputs.contains(idx) {
+                format!("Node {idx} is unused but not declared as output")
+            } else {
+                format!("Node {idx} is declared as output but has successors")
+            }));
+        }
+        Ok(self.circuit)
+    }
+}
+
+fn err_invalid_circuit(msg: impl Into<String>) -> Error {
+    Error::InvalidCircuit(msg.into())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            builder::CircuitBuilder,
+            node::{ConstraintNode, VanillaGate, VanillaNode},
+        },
+        dev::run_gkr,
+        poly::box_dense_poly,
+        util::{
+            dev::{rand_vec, seeded_std_rng},
+            expression::Expression,
+        },
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn builder() {
+        let mut rng = seeded_std_rng();
+        let hadamard = || VanillaNode::new(2, 0, vec![VanillaGate::mul((0, 0), (1, 0))], 1 << 4);
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let x = builder.input(4);
+        let y = builder.input(4);
+        let z = builder.vanilla(hadamard(), &[x, y]).unwrap();
+        assert!(matches!(
+            builder.vanilla(hadamard(), &[z]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            builder.vanilla(hadamard(), &[z, z]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        let w = builder.input(5);
+        assert!(matches!(
+            builder.vanilla(hadamard(), &[z, w]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        let w = builder.input(4);
+        let out = builder.vanilla(hadamard(), &[z, w]).unwrap();
+        assert!(CircuitBuilder::<Goldilocks, GoldilocksExt2>::new()
+            .build()
+            .is_ok());
+
+        builder.output(out);
+        assert!(matches!(builder.build(), Err(Error::InvalidCircuit(_))));
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [x, y, w] = [(); 3].map(|_| builder.input(4));
+        let z = builder.vanilla(hadamard(), &[x, y]).unwrap();
+        let out = builder.vanilla(hadamard(), &[z, w]).unwrap();
+        builder.output(out);
+        let circuit = builder.build().unwrap();
+        let inputs = [(); 3].map(|_| box_dense_poly(rand_vec(1 << 4, &mut rng)));
+        run_gkr(&circuit, &inputs, &mut rng);
+    }
+
+    #[test]
+    fn constraint_inputs() {
+        let [a, b, c] = [0, 1, 2].map(Expression::<GoldilocksExt2, _>::poly);
+        let constraint = || ConstraintNode::new(4, &a * &b - &c);
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [x, y, z] = [(); 3].map(|_| builder.input(4));
+        let w = builder.input(3);
+        assert!(matches!(
+            builder.node(constraint(), &[x, y]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            builder.node(constraint(), &[x, y, w]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        assert!(builder.node(constraint(), &[x, y, z]).is_ok());
+    }
+}
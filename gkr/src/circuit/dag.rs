@@ -1,4 +1,4 @@
-use crate::util::{chain, Itertools};
+use crate::util::{chain, izip, Itertools};
 
 #[derive(Clone, Debug)]
 pub struct DirectedAcyclicGraph<T> {
@@ -20,6 +20,46 @@ impl<T> DirectedAcyclicGraph<T> {
         Self { nodes, edges }
     }
 
+    // Returns `None` if `inputs` and `outputs` disagree on any edge or contain
+    // a cycle.
+    pub(super) fn from_adjacency(
+        nodes: Vec<T>,
+        inputs: Vec<Vec<usize>>,
+        outputs: Vec<Vec<usize>>,
+    ) -> Option<Self> {
+        if inputs.len() != nodes.len() || outputs.len() != nodes.len() {
+            return None;
+        }
+        let has_edge = |adjs: &[Vec<usize>], from: usize, to: usize| {
+            adjs.get(from).is_some_and(|adjs| adjs.contains(&to))
+        };
+        let consistent = (0..nodes.len()).all(|idx| {
+            inputs[idx]
+                .iter()
+                .all(|from| *from != idx && has_edge(&outputs, *from, idx))
+                && outputs[idx]
+                    .iter()
+                    .all(|to| *to != idx && has_edge(&inputs, *to, idx))
+                && inputs[idx].iter().all_unique()
+                && outputs[idx].iter().all_unique()
+        });
+        if !consistent {
+            return None;
+        }
+
+        let edges = izip!(inputs, outputs)
+            .map(|(inputs, outputs)| {
+                chain![
+                    outputs.into_iter().map(|to| (to, Direction::Out)),
+                    inputs.into_iter().map(|from| (from, Direction::In)),
+                ]
+                .collect()
+            })
+            .collect();
+        let dag = Self { nodes, edges };
+        dag.is_acyclic().then_some(dag)
+    }
+
     pub fn nodes(&self) -> &[T] {
         &self.nodes
     }
@@ -53,6 +93,18 @@ impl<T> DirectedAcyclicGraph<T> {
     }
 
     pub(super) fn topo(&self) -> Vec<usize> {
+        let topo = self.try_topo();
+
+        assert_eq!(topo.len(), self.nodes.len());
+
+        topo
+    }
+
+    fn is_acyclic(&self) -> bool {
+        self.try_topo().len() == self.nodes.len()
+    }
+
+    fn try_topo(&self) -> Vec<usize> {
         let mut topo = Vec::with_capacity(self.nodes.len());
         let mut indegs = self.indegs().collect_vec();
         let mut queue = self.inputs().collect_vec();
@@ -67,8 +119,6 @@ impl<T> DirectedAcyclicGraph<T> {
             });
         }
 
-        assert_eq!(topo.len(), self.nodes.len());
-
         topo
     }
 
@@ -116,6 +166,12 @@ impl<T> Default for DirectedAcyclicGraph<T> {
 #[derive(Clone, Copy, Debug)]
 pub struct NodeId(pub(super) usize);
 
+impl From<NodeId> for usize {
+    fn from(NodeId(id): NodeId) -> Self {
+        id
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Direction {
     In,
@@ -0,0 +1,360 @@
+use crate::{
+    circuit::{
+        dag::DirectedAcyclicGraph,
+        node::{
+            ConstantNode, ConstraintNode, FftNode, InputNode, LogUpNode, NodeExt, VanillaGate,
+            VanillaNode,
+        },
+        Circuit,
+    },
+    util::{
+        arithmetic::{ExtensionField, PrimeField},
+        expression::{Expression, ExpressionRegistry},
+        izip, Itertools,
+    },
+    Error,
+};
+
+/// Structural description of a [`Circuit`], enough to rebuild it without the
+/// code that constructed it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitDescription<F> {
+    nodes: Vec<NodeDescription<F>>,
+    inputs: Vec<Vec<usize>>,
+    outputs: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeDescription<F> {
+    Input {
+        log2_size: usize,
+        log2_reps: usize,
+    },
+    Vanilla {
+        input_arity: usize,
+        log2_sub_input_size: usize,
+        log2_reps: usize,
+        gates: Vec<VanillaGate<F>>,
+    },
+    LogUp {
+        log2_t_size: usize,
+        log2_f_size: usize,
+        num_fs: usize,
+    },
+    Fft {
+        log2_size: usize,
+        inverse: bool,
+    },
+    Constant {
+        values: Vec<F>,
+    },
+    Constraint {
+        log2_size: usize,
+        expression: Expression<F, usize>,
+    },
+}
+
+impl<F> CircuitDescription<F> {
+    pub fn nodes(&self) -> &[NodeDescription<F>] {
+        &self.nodes
+    }
+}
+
+impl<F: PrimeField, E: ExtensionField<F>> Circuit<F, E> {
+    /// Returns the description of the circuit, failing if any node doesn't
+    /// implement [`Node::description`](crate::circuit::node::Node::description).
+    pub fn description(&self) -> Result<CircuitDescription<F>, Error> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                node.description().ok_or_else(|| {
+                    Error::InvalidCircuit(format!("Node {idx} ({node:?}) is not describable"))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let (inputs, outputs) = (0..self.nodes().len())
+            .map(|idx| (self.predec(idx).collect(), self.succ(idx).collect()))
+            .unzip();
+        Ok(CircuitDescription {
+            nodes,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Rebuilds a circuit from `description`, keeping the order of edges so
+    /// the rebuilt circuit produces and accepts the same proofs.
+    pub fn from_description(description: CircuitDescription<F>) -> Result<Self, Error> {
+        let CircuitDescription {
+            nodes,
+            inputs,
+            outputs,
+        } = description;
+        for (idx, node) in nodes.iter().enumerate() {
+            validate(node).map_err(|err| Error::InvalidCircuit(format!("Node {idx}: {err}")))?;
+        }
+        let nodes = nodes
+            .into_iter()
+            .map(|node| match node {
+                NodeDescription::Input {
+                    log2_size,
+                    log2_reps,
+                } => InputNode::new(log2_size, 1 << log2_reps).boxed(),
+                NodeDescription::Vanilla {
+                    input_arity,
+                    log2_sub_input_size,
+                    log2_reps,
+                    gates,
+                } => VanillaNode::new(input_arity, log2_sub_input_size, gates, 1 << log2_reps)
+                    .boxed(),
+                NodeDescription::LogUp {
+                    log2_t_size,
+                    log2_f_size,
+                    num_fs,
+                } => LogUpNode::new(log2_t_size, log2_f_size, num_fs).boxed(),
+                NodeDescription::Fft { log2_size, inverse } => match inverse {
+                    false => FftNode::forward(log2_size).boxed(),
+                    true => FftNode::inverse(log2_size).boxed(),
+                },
+                NodeDescription::Constant { values } => ConstantNode::new(values).boxed(),
+                NodeDescription::Constraint {
+                    log2_size,
+                    expression,
+                } => ConstraintNode::new(log2_size, expression.lift()).boxed(),
+            })
+            .collect();
+
+        let dag = DirectedAcyclicGraph::from_adjacency(nodes, inputs, outputs)
+            .ok_or_else(|| Error::InvalidCircuit("Inconsistent or cyclic edges".to_string()))?;
+        for (to, node) in dag.nodes().iter().enumerate() {
+            let arity = dag.predec(to).count();
+            if arity != node.input_arity() {
+                return Err(Error::InvalidCircuit(format!(
+                    "Node {to} expects {} inputs but has {arity}",
+                    node.input_arity()
+                )));
+            }
+            if let Some(from) = node.log2_input_sizes().and_then(|log2_sizes| {
+                izip!(dag.predec(to), log2_sizes)
+                    .find(|(from, log2_size)| dag.nodes()[*from].log2_output_size() != *log2_size)
+                    .map(|(from, _)| from)
+            }) {
+                return Err(Error::InvalidCircuit(format!(
+                    "Output of node {from} mismatches input size of node {to}"
+                )));
+            }
+            if let Some(from) = dag
+                .predec(to)
+                .find(|from| dag.nodes()[*from].log2_output_size() > node.log2_input_size())
+            {
+                return Err(Error::InvalidCircuit(format!(
+                    "Output of node {from} exceeds input size of node {to}"
+                )));
+            }
+        }
+        let topo = dag.topo();
+        Ok(Self { dag, topo })
+    }
+}
+
+// Checks what node constructors would otherwise assert, so a malformed
+// description fails with an error instead of a panic.
+fn validate<F: PrimeField>(node: &NodeDescription<F>) -> Result<(), String> {
+    let check = |condition: bool, err: &str| condition.then_some(()).ok_or_else(|| err.to_string());
+    let fits = |log2_sizes: &[usize]| {
+        let log2_size = log2_sizes
+            .iter()
+            .try_fold(0usize, |acc, v| acc.checked_add(*v));
+        check(
+            log2_size.is_some_and(|log2_size| log2_size < usize::BITS as usize),
+            "Size overflows usize",
+        )
+    };
+    match node {
+        NodeDescription::Input {
+            log2_size,
+            log2_reps,
+        } => fits(&[*log2_size, *log2_reps]),
+        NodeDescription::Vanilla {
+            input_arity,
+            log2_sub_input_size,
+            log2_reps,
+            gates,
+        } => {
+            check(!gates.is_empty(), "No gates")?;
+            let log2_sub_output_size = gates.len().next_power_of_two().ilog2() as usize;
+            fits(&[*log2_sub_input_size, *log2_reps])?;
+            fits(&[log2_sub_output_size, *log2_reps])?;
+            let wires = gates.iter().flat_map(VanillaGate::wires).collect_vec();
+            check(
+                wires
+                    .iter()
+                    .all(|(i, b)| i < input_arity && *b < 1 << log2_sub_input_size),
+                "Wire out of range",
+            )?;
+            check(
+                wires.iter().map(|(i, _)| i).unique().count() == *input_arity,
+                "Gates don't use every input",
+            )
+        }
+        NodeDescription::LogUp {
+            log2_t_size,
+            log2_f_size,
+            num_fs,
+        } => {
+            check(*num_fs != 0, "No lookup inputs")?;
+            fits(&[*log2_t_size])?;
+            fits(&[*log2_f_size, num_fs.next_power_of_two().ilog2() as usize])
+        }
+        NodeDescription::Fft { log2_size, .. } => check(
+            *log2_size <= F::S as usize,
+            "FFT size exceeds the two-adicity of the field",
+        ),
+        NodeDescription::Constant { values } => {
+            check(values.len().is_power_of_two(), "Values not a power of two")
+        }
+        NodeDescription::Constraint {
+            log2_size,
+            expression,
+        } => {
+            check(*log2_size > 0, "Empty constraint")?;
+            fits(&[*log2_size])?;
+            check(expression.degree() >= 1, "Expression refers to no input")?;
+            let registry = ExpressionRegistry::new(expression);
+            let num_inputs = registry.datas().len();
+            check(
+                registry.datas().iter().all(|poly| *poly < num_inputs),
+                "Expression skips an input",
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            node::{EvalClaim, FftNode, VanillaGate},
+            Circuit, CircuitBuilder, NodeDescription,
+        },
+        dev::grand_product_circuit,
+        poly::MultilinearPoly,
+        prove_gkr,
+        transcript::StdRngTranscript,
+        util::{
+            arithmetic::{Field, PrimeField},
+            dev::seeded_std_rng,
+        },
+        verify_gkr, Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn description() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let description = circuit.description().unwrap();
+        let rebuilt =
+            Circuit::<Goldilocks, GoldilocksExt2>::from_description(description.clone()).unwrap();
+        assert_eq!(rebuilt.description().unwrap(), description);
+
+        let output_claims = vec![EvalClaim::new(vec![], values[4].evaluate(&[]))];
+        let proof = {
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        assert!(verify_gkr(&rebuilt, &output_claims, &mut transcript).is_ok());
+
+        let circuit = {
+            let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+            let [m, t, f] = [(); 3].map(|_| builder.input(3));
+            let f = builder.node(FftNode::inverse(3), &[f]).unwrap();
+            let out = builder.log_up(m, t, &[f]).unwrap();
+            builder.output(out);
+            builder.build().unwrap()
+        };
+        let description = circuit.description().unwrap();
+        assert_eq!(
+            Circuit::<Goldilocks, GoldilocksExt2>::from_description(description.clone())
+                .unwrap()
+                .description()
+                .unwrap(),
+            description
+        );
+
+        let mut fft_arity = description.clone();
+        fft_arity.inputs[3].push(1);
+        fft_arity.outputs[1].push(3);
+        assert!(matches!(
+            Circuit::<Goldilocks, GoldilocksExt2>::from_description(fft_arity),
+            Err(Error::InvalidCircuit(_))
+        ));
+
+        let mut cyclic = description.clone();
+        cyclic.inputs[2].push(4);
+        cyclic.outputs[4].push(2);
+        assert!(matches!(
+            Circuit::<Goldilocks, GoldilocksExt2>::from_description(cyclic),
+            Err(Error::InvalidCircuit(_))
+        ));
+
+        let malformed = [
+            NodeDescription::Input {
+                log2_size: 3,
+                log2_reps: usize::BITS as usize,
+            },
+            NodeDescription::Vanilla {
+                input_arity: 1,
+                log2_sub_input_size: 1,
+                log2_reps: 2,
+                gates: vec![VanillaGate::relay((0, 2))],
+            },
+            NodeDescription::Fft {
+                log2_size: Goldilocks::S as usize + 1,
+                inverse: false,
+            },
+            NodeDescription::Constant {
+                values: vec![Goldilocks::ONE; 3],
+            },
+        ];
+        for node in malformed {
+            let mut description = description.clone();
+            description.nodes[3] = node;
+            assert!(matches!(
+                Circuit::<Goldilocks, GoldilocksExt2>::from_description(description),
+                Err(Error::InvalidCircuit(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::circuit::CircuitDescription;
+        use halo2_curves::bn256::Fr;
+
+        let (circuit, _) = grand_product_circuit::<Fr, Fr>(3, &mut seeded_std_rng());
+        let description = circuit.description().unwrap();
+        let bytes = serde_json::to_vec(&description).unwrap();
+        let deserialized = serde_json::from_slice::<CircuitDescription<Fr>>(&bytes).unwrap();
+        assert_eq!(deserialized, description);
+        assert!(Circuit::<Fr, Fr>::from_description(deserialized).is_ok());
+
+        // Node 1 loses its only input, which `verify_gkr` would otherwise
+        // panic on.
+        let mut json = serde_json::to_value(&description).unwrap();
+        json["inputs"][1] = serde_json::json!([]);
+        json["outputs"][0] = serde_json::json!([]);
+        let mismatched = serde_json::from_value::<CircuitDescription<Fr>>(json).unwrap();
+        assert!(matches!(
+            Circuit::<Fr, Fr>::from_description(mismatched),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+}
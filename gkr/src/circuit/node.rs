@@ -1,4 +1,5 @@
 use crate::{
+    circuit::NodeDescription,
     poly::BoxMultilinearPoly,
     transcript::{TranscriptRead, TranscriptWrite},
     util::arithmetic::{inner_product, Field},
@@ -6,18 +7,22 @@ use crate::{
 };
 use std::fmt::Debug;
 
+mod constant;
+mod constraint;
 mod fft;
 mod input;
 mod log_up;
 mod vanilla;
 
+pub use constant::ConstantNode;
+pub use constraint::ConstraintNode;
 pub use fft::FftNode;
 pub use input::InputNode;
 pub use log_up::LogUpNode;
 pub use vanilla::{VanillaGate, VanillaNode};
 
 #[auto_impl::auto_impl(&, Box)]
-pub trait Node<F, E>: Debug {
+pub trait Node<F, E>: Debug + Send + Sync {
     fn input_size(&self) -> usize {
         1 << self.log2_input_size()
     }
@@ -28,8 +33,17 @@ pub trait Node<F, E>: Debug {
 
     fn is_input(&self) -> bool;
 
+    /// Returns the number of inputs the node takes.
+    fn input_arity(&self) -> usize;
+
     fn log2_input_size(&self) -> usize;
 
+    /// Returns the log2 size each input must have, if the node doesn't accept
+    /// inputs smaller than [`Node::input_size`].
+    fn log2_input_sizes(&self) -> Option<Vec<usize>> {
+        None
+    }
+
     fn log2_output_size(&self) -> usize;
 
     fn evaluate(&self, inputs: Vec<&BoxMultilinearPoly<F, E>>)
@@ -47,6 +61,17 @@ pub trait Node<F, E>: Debug {
         claim: CombinedEvalClaim<E>,
         transcript: &mut dyn TranscriptRead<F, E>,
     ) -> Result<Vec<Vec<EvalClaim<E>>>, Error>;
+
+    fn name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        let name = name.split_once('<').map_or(name, |(name, _)| name);
+        name.rsplit("::").next().unwrap().to_string()
+    }
+
+    /// Returns the structural description used to rebuild the node, if any.
+    fn description(&self) -> Option<NodeDescription<F>> {
+        None
+    }
 }
 
 pub trait NodeExt<F, E>: Node<F, E> {
@@ -67,7 +92,7 @@ impl<F, E, N: Node<F, E>> NodeExt<F, E> for N {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct EvalClaim<F> {
     point: Vec<F>,
     value: F,
@@ -0,0 +1,144 @@
+use crate::{
+    circuit::{
+        node::{CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
+    poly::{box_dense_poly, evaluate, BoxMultilinearPoly, Padding},
+    transcript::{TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{inner_product, ExtensionField, Field},
+        Itertools,
+    },
+    Error,
+};
+use std::marker::PhantomData;
+
+/// Node without inputs holding verifier-known values (round constants,
+/// selectors), whose claims are checked by the verifier evaluating them
+/// directly.
+#[derive(Clone, Debug)]
+pub struct ConstantNode<F, E> {
+    log2_size: usize,
+    values: Vec<F>,
+    _marker: PhantomData<E>,
+}
+
+impl<F: Field, E: ExtensionField<F>> ConstantNode<F, E> {
+    pub fn new(values: Vec<F>) -> Self {
+        assert!(values.len().is_power_of_two());
+
+        Self {
+            log2_size: values.len().ilog2() as usize,
+            values,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pads `values` to a power of two with `padding`, so the verifier's
+    /// evaluations agree with the padded values the prover uses.
+    pub fn padded(values: Vec<F>, padding: Padding<F>) -> Self {
+        Self::new(padding.pad(values))
+    }
+
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Node<F, E> for ConstantNode<F, E> {
+    fn is_input(&self) -> bool {
+        false
+    }
+
+    fn input_arity(&self) -> usize {
+        0
+    }
+
+    fn log2_input_size(&self) -> usize {
+        self.log2_size
+    }
+
+    fn log2_output_size(&self) -> usize {
+        self.log2_size
+    }
+
+    fn evaluate(
+        &self,
+        inputs: Vec<&BoxMultilinearPoly<F, E>>,
+    ) -> BoxMultilinearPoly<'static, F, E> {
+        assert!(inputs.is_empty());
+
+        box_dense_poly(self.values.clone())
+    }
+
+    fn prove_claim_reduction(
+        &self,
+        _: CombinedEvalClaim<E>,
+        inputs: Vec<&BoxMultilinearPoly<F, E>>,
+        _: &mut dyn TranscriptWrite<F, E>,
+    ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+        assert!(inputs.is_empty());
+
+        Ok(Vec::new())
+    }
+
+    fn verify_claim_reduction(
+        &self,
+        claim: CombinedEvalClaim<E>,
+        _: &mut dyn TranscriptRead<F, E>,
+    ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+        let evals = claim
+            .points
+            .iter()
+            .map(|point| evaluate(&self.values, point))
+            .collect_vec();
+        if claim.value != inner_product::<E, E>(&claim.alphas, &evals) {
+            return Err(Error::InvalidProof(
+                "Unmatched evaluation of constant node".to_string(),
+            ));
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn description(&self) -> Option<NodeDescription<F>> {
+        Some(NodeDescription::Constant {
+            values: self.values.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            node::{ConstantNode, VanillaGate, VanillaNode},
+            CircuitBuilder,
+        },
+        dev::run_gkr,
+        poly::{box_dense_poly, Padding},
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn constant() {
+        let mut rng = seeded_std_rng();
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let x = builder.input(4);
+        let c = builder.constant(ConstantNode::padded(
+            rand_vec(13, &mut rng),
+            Padding::RepeatLast,
+        ));
+        let gates = vec![VanillaGate::mul((0, 0), (1, 0))];
+        let xc = builder
+            .vanilla(VanillaNode::new(2, 0, gates, 1 << 4), &[x, c])
+            .unwrap();
+        builder.output(xc);
+        let circuit = builder.build().unwrap();
+        assert_eq!(circuit.inputs().count(), 1);
+
+        let inputs = vec![box_dense_poly(rand_vec(1 << 4, &mut rng))];
+        run_gkr(&circuit, &inputs, &mut rng);
+    }
+}
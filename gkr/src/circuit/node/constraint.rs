@@ -0,0 +1,265 @@
+use crate::{
+    circuit::{
+        node::{CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
+    poly::{box_dense_poly, BoxMultilinearPoly},
+    sum_check::{
+        err_unmatched_evaluation, generic::Generic, prove_sum_check, verify_sum_check,
+        SumCheckFunction, SumCheckPoly,
+    },
+    transcript::{TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{ExtensionField, Field},
+        expression::{Expression, ExpressionRegistry},
+        Itertools,
+    },
+    Error,
+};
+use std::marker::PhantomData;
+
+/// Node proving `expression` over its inputs vanishes on the hypercube via
+/// zero-check, where `Expression::poly(i)` refers to the `i`-th input.
+///
+/// Its output is a single zero, like [`LogUpNode`](super::LogUpNode).
+#[derive(Clone, Debug)]
+pub struct ConstraintNode<F, E> {
+    log2_size: usize,
+    num_inputs: usize,
+    expression: Expression<E, usize>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field, E: ExtensionField<F>> ConstraintNode<F, E> {
+    pub fn new(log2_size: usize, expression: Expression<E, usize>) -> Self {
+        assert!(log2_size > 0);
+        assert!(expression.degree() >= 1, "Expression refers to no input");
+        let registry = ExpressionRegistry::new(&expression);
+        let num_inputs = registry.datas().len();
+        assert!(registry.datas().iter().all(|poly| *poly < num_inputs));
+
+        Self {
+            log2_size,
+            num_inputs,
+            expression,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn expression(&self) -> &Expression<E, usize> {
+        &self.expression
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Node<F, E> for ConstraintNode<F, E> {
+    fn is_input(&self) -> bool {
+        false
+    }
+
+    fn input_arity(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn log2_input_size(&self) -> usize {
+        self.log2_size
+    }
+
+    fn log2_input_sizes(&self) -> Option<Vec<usize>> {
+        Some(vec![self.log2_size; self.num_inputs])
+    }
+
+    fn log2_output_size(&self) -> usize {
+        0
+    }
+
+    fn evaluate(&self, _: Vec<&BoxMultilinearPoly<F, E>>) -> BoxMultilinearPoly<'static, F, E> {
+        box_dense_poly([F::ZERO])
+    }
+
+    #[tracing::instrument(skip_all, name = "ConstraintNode::prove_claim_reduction")]
+    fn prove_claim_reduction(
+        &self,
+        _: CombinedEvalClaim<E>,
+        inputs: Vec<&BoxMultilinearPoly<F, E>>,
+        transcript: &mut dyn TranscriptWrite<F, E>,
+    ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+        assert_eq!(inputs.len(), self.num_inputs);
+        assert!(!inputs.iter().any(|input| input.len() != self.input_size()));
+
+        let r = transcript.squeeze_challenges(self.log2_size);
+        let g = Generic::new(self.log2_size, &self.expression).mul_by_eq(&r, true);
+        let polys = inputs
+            .into_iter()
+            .map(SumCheckPoly::<_, _, _, BoxMultilinearPoly<E>>::Base);
+        let (_, r_x, evals) = prove_sum_check(&g, E::ZERO, polys, transcript)?;
+        transcript.write_felt_exts(&evals)?;
+
+        Ok(self.input_claims(r_x, evals))
+    }
+
+    #[tracing::instrument(skip_all, name = "ConstraintNode::verify_claim_reduction")]
+    fn verify_claim_reduction(
+        &self,
+        claim: CombinedEvalClaim<E>,
+        transcript: &mut dyn TranscriptRead<F, E>,
+    ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+        if claim.value != E::ZERO {
+            return Err(Error::InvalidProof(
+                "Non-zero ConstraintNode output claim".to_string(),
+            ));
+        }
+
+        let r = transcript.squeeze_challenges(self.log2_size);
+        let g = Generic::new(self.log2_size, &self.expression).mul_by_eq(&r, false);
+        let (sub_claim, r_x) = verify_sum_check(&g, E::ZERO, transcript)?;
+        let evals = transcript.read_felt_exts(self.num_inputs)?;
+        let expected = g.evaluate(&evals);
+        if sub_claim != expected {
+            return Err(err_unmatched_evaluation(&expected, &sub_claim));
+        }
+
+        Ok(self.input_claims(r_x, evals))
+    }
+
+    /// Descriptions are over `F`, so only expressions whose constants are all
+    /// in `F` are describable.
+    fn description(&self) -> Option<NodeDescription<F>> {
+        let is_base = |constant: E| constant.as_bases()[1..].iter().all(|base| *base == F::ZERO);
+        let is_describable = self.expression.evaluate(
+            &is_base,
+            &|_| true,
+            &|value| value,
+            &|lhs, rhs| lhs && rhs,
+            &|lhs, rhs| lhs && rhs,
+        );
+        is_describable.then(|| NodeDescription::Constraint {
+            log2_size: self.log2_size,
+            expression: self
+                .expression
+                .map_constants(&|constant| constant.as_bases()[0]),
+        })
+    }
+}
+
+impl<F, E: Clone> ConstraintNode<F, E> {
+    fn input_claims(&self, r_x: Vec<E>, evals: Vec<E>) -> Vec<Vec<EvalClaim<E>>> {
+        evals
+            .into_iter()
+            .map(|eval| vec![EvalClaim::new(r_x.clone(), eval)])
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            node::{ConstraintNode, EvalClaim},
+            Circuit, CircuitBuilder,
+        },
+        dev::run_gkr,
+        poly::{box_dense_poly, MultilinearPoly},
+        prove_gkr,
+        transcript::StdRngTranscript,
+        util::{
+            arithmetic::Field,
+            dev::{rand_vec, seeded_std_rng},
+            expression::Expression,
+            Itertools,
+        },
+        verify_gkr, Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn constraint() {
+        let mut rng = seeded_std_rng();
+        let log2_size = 5;
+        let [a, b, c] = [0, 1, 2].map(Expression::<GoldilocksExt2, _>::poly);
+        let expression = &a * &a * &b - &c + Expression::constant(GoldilocksExt2::ONE);
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let inputs = [(); 3].map(|_| builder.input(log2_size));
+        let out = builder
+            .node(ConstraintNode::new(log2_size, expression), &inputs)
+            .unwrap();
+        builder.output(out);
+        let circuit = builder.build().unwrap();
+
+        let [a, b] = [(); 2].map(|_| rand_vec::<Goldilocks>(1 << log2_size, &mut rng));
+        let c = (0..1 << log2_size)
+            .map(|b_x| a[b_x] * a[b_x] * b[b_x] + Goldilocks::ONE)
+            .collect_vec();
+        run_gkr(
+            &circuit,
+            &[a.clone(), b.clone(), c.clone()].map(box_dense_poly),
+            &mut rng,
+        );
+
+        let values = circuit.evaluate(
+            [&a, &b, &c]
+                .into_iter()
+                .map(|value| box_dense_poly(value.clone()))
+                .collect(),
+        );
+        let proof = {
+            let output_claims = vec![EvalClaim::new(vec![], GoldilocksExt2::ZERO)];
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let output_claims = vec![EvalClaim::new(vec![], GoldilocksExt2::ONE)];
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        assert!(matches!(
+            verify_gkr(&circuit, &output_claims, &mut transcript),
+            Err(Error::InvalidProof(_))
+        ));
+
+        // The prover asserts every sum-check claim with sanity-check enabled
+        if cfg!(feature = "sanity-check") {
+            return;
+        }
+        let mut wrong_c = c;
+        wrong_c[7] += Goldilocks::ONE;
+        let values = circuit.evaluate([a, b, wrong_c].into_iter().map(box_dense_poly).collect());
+        let output_claims = vec![EvalClaim::new(vec![], values[3].evaluate(&[]))];
+        let proof = {
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        assert!(verify_gkr(&circuit, &output_claims, &mut transcript).is_err());
+    }
+
+    #[test]
+    fn linear_constraint() {
+        let mut rng = seeded_std_rng();
+        let log2_size = 4;
+        let [a, b, c] = [0, 1, 2].map(Expression::<GoldilocksExt2, _>::poly);
+        let two = Expression::constant(GoldilocksExt2::from(2));
+        let expression = a + two * b - c;
+
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let inputs = [(); 3].map(|_| builder.input(log2_size));
+        let out = builder
+            .node(ConstraintNode::new(log2_size, expression), &inputs)
+            .unwrap();
+        builder.output(out);
+        let circuit = builder.build().unwrap();
+
+        let description = circuit.description().unwrap();
+        let rebuilt = Circuit::<_, GoldilocksExt2>::from_description(description.clone()).unwrap();
+        assert_eq!(rebuilt.description().unwrap(), description);
+
+        let [a, b] = [(); 2].map(|_| rand_vec::<Goldilocks>(1 << log2_size, &mut rng));
+        let c = (0..1 << log2_size)
+            .map(|b_x| a[b_x] + b[b_x].double())
+            .collect_vec();
+        run_gkr(&rebuilt, &[a, b, c].map(box_dense_poly), &mut rng);
+    }
+}
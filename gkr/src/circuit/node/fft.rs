@@ -3,7 +3,10 @@
 //! [LXZ21]: https://eprint.iacr.org/2021/673
 
 use crate::{
-    circuit::node::{CombinedEvalClaim, EvalClaim, Node},
+    circuit::{
+        node::{CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
     poly::{box_dense_poly, eq_eval, eq_poly, evaluate, repeated_dense_poly, BoxMultilinearPoly},
     sum_check::{
         err_unmatched_evaluation, generic::Generic, prove_sum_check, quadratic::Quadratic,
@@ -38,10 +41,18 @@ impl<F: PrimeField, E: ExtensionField<F>> Node<F, E> for FftNode<F, E> {
         false
     }
 
+    fn input_arity(&self) -> usize {
+        1
+    }
+
     fn log2_input_size(&self) -> usize {
         self.log2_size
     }
 
+    fn log2_input_sizes(&self) -> Option<Vec<usize>> {
+        Some(vec![self.log2_size])
+    }
+
     fn log2_output_size(&self) -> usize {
         self.log2_size
     }
@@ -109,8 +120,9 @@ impl<F: PrimeField, E: ExtensionField<F>> Node<F, E> for FftNode<F, E> {
             let (sub_claim, r_x) = verify_sum_check(&g, claim.value, transcript)?;
             let input_r_x = transcript.read_felt_ext()?;
             let w_r_xs = transcript.read_felt_exts(claim.points.len())?;
-            if sub_claim != self.final_eval(&claim, input_r_x, &w_r_xs) {
-                return Err(err_unmatched_evaluation());
+            let expected = self.final_eval(&claim, input_r_x, &w_r_xs);
+            if sub_claim != expected {
+                return Err(err_unmatched_evaluation(&expected, &sub_claim));
             }
 
             (r_x, input_r_x, w_r_xs)
@@ -120,6 +132,13 @@ impl<F: PrimeField, E: ExtensionField<F>> Node<F, E> for FftNode<F, E> {
 
         Ok(vec![vec![EvalClaim::new(r_x, input_r_x)]])
     }
+
+    fn description(&self) -> Option<NodeDescription<F>> {
+        Some(NodeDescription::Fft {
+            log2_size: self.log2_size,
+            inverse: self.n_inv.is_some(),
+        })
+    }
 }
 
 impl<F: PrimeField, E: ExtensionField<F>> FftNode<F, E> {
@@ -230,10 +249,10 @@ impl<F: PrimeField, E: ExtensionField<F>> FftNode<F, E> {
             } else {
                 transcript.read_felt_exts(r_gs.len())?
             };
-            if sub_claim
-                != self.wiring_sum_check_final_eval(&g, &r_x, &r_x_prime, &w_interm_r_x_primes)
-            {
-                return Err(err_unmatched_evaluation());
+            let expected =
+                self.wiring_sum_check_final_eval(&g, &r_x, &r_x_prime, &w_interm_r_x_primes);
+            if sub_claim != expected {
+                return Err(err_unmatched_evaluation(&expected, &sub_claim));
             }
             claims = w_interm_r_x_primes;
             r_x = r_x_prime[..r_x_prime.len() - 1].to_vec();
@@ -323,13 +342,14 @@ mod test {
                 input::InputNode,
                 NodeExt,
             },
-            test::{run_circuit, TestData},
+            test::{run_circuit_over_fields, TestData},
             Circuit,
         },
+        dev::fuzz_verifier,
         poly::box_dense_poly,
         util::{
             arithmetic::{radix2_fft, ExtensionField, PrimeField},
-            dev::rand_vec,
+            dev::{rand_vec, seeded_std_rng},
             RngCore,
         },
     };
@@ -337,7 +357,15 @@ mod test {
 
     #[test]
     fn fft_and_then_ifft() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(fft_and_then_ifft_circuit);
+        run_circuit_over_fields!(fft_and_then_ifft_circuit);
+    }
+
+    #[test]
+    fn fuzz() {
+        let mut rng = seeded_std_rng();
+        let (circuit, inputs, _) =
+            fft_and_then_ifft_circuit::<Goldilocks, GoldilocksExt2>(4, &mut rng);
+        fuzz_verifier(&circuit, &circuit.evaluate(inputs), 64, &mut rng).unwrap();
     }
 
     pub fn fft_and_then_ifft_circuit<F: PrimeField, E: ExtensionField<F>>(
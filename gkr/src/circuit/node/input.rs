@@ -1,5 +1,8 @@
 use crate::{
-    circuit::node::{CombinedEvalClaim, EvalClaim, Node},
+    circuit::{
+        node::{CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
     poly::BoxMultilinearPoly,
     transcript::{TranscriptRead, TranscriptWrite},
     Error,
@@ -27,6 +30,10 @@ impl<F, E> Node<F, E> for InputNode {
         true
     }
 
+    fn input_arity(&self) -> usize {
+        0
+    }
+
     fn log2_input_size(&self) -> usize {
         self.log2_size + self.log2_reps
     }
@@ -55,4 +62,11 @@ impl<F, E> Node<F, E> for InputNode {
     ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
         unreachable!()
     }
+
+    fn description(&self) -> Option<NodeDescription<F>> {
+        Some(NodeDescription::Input {
+            log2_size: self.log2_size,
+            log2_reps: self.log2_reps,
+        })
+    }
 }
@@ -1,10 +1,14 @@
 use crate::{
-    circuit::node::{log_up::LogUpState::*, CombinedEvalClaim, EvalClaim, Node},
+    circuit::{
+        node::{log_up::LogUpState::*, CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
     izip_par,
     poly::{box_dense_poly, merge, BoxMultilinearPoly, MultilinearPoly},
     sum_check::{
         err_unmatched_evaluation, generic::Generic, prove_sum_check, quadratic::Quadratic,
-        verify_sum_check, SumCheckFunction, SumCheckFunctionExt, SumCheckPoly,
+        verify_sum_check, SumCheckError, SumCheckFunction, SumCheckFunctionExt, SumCheckPhase,
+        SumCheckPoly,
     },
     transcript::{Transcript, TranscriptRead, TranscriptWrite},
     util::{
@@ -30,10 +34,19 @@ impl<F: Field, E: ExtensionField<F>> Node<F, E> for LogUpNode {
         false
     }
 
+    fn input_arity(&self) -> usize {
+        2 + self.num_fs
+    }
+
     fn log2_input_size(&self) -> usize {
         self.log2_t_size.max(self.log2_f_size)
     }
 
+    fn log2_input_sizes(&self) -> Option<Vec<usize>> {
+        let (t, f) = (self.log2_t_size, self.log2_f_size);
+        Some([vec![t; 2], vec![f; self.num_fs]].concat())
+    }
+
     fn log2_output_size(&self) -> usize {
         0
     }
@@ -284,8 +297,9 @@ impl<F: Field, E: ExtensionField<F>> Node<F, E> for LogUpNode {
                     Finished => Vec::new(),
                 };
 
-                if sub_claim != g.evaluate(&chain![&m_t_evals, &f_evals].copied().collect_vec()) {
-                    return Err(err_unmatched_evaluation());
+                let expected = g.evaluate(&chain![&m_t_evals, &f_evals].copied().collect_vec());
+                if sub_claim != expected {
+                    return Err(err_unmatched_evaluation(&expected, &sub_claim));
                 }
 
                 (r_prime, m_t_evals, f_evals)
@@ -310,6 +324,14 @@ impl<F: Field, E: ExtensionField<F>> Node<F, E> for LogUpNode {
         .map(|(r, value)| vec![EvalClaim::new(r, value)])
         .collect())
     }
+
+    fn description(&self) -> Option<NodeDescription<F>> {
+        Some(NodeDescription::LogUp {
+            log2_t_size: self.log2_t_size,
+            log2_f_size: self.log2_f_size,
+            num_fs: self.num_fs,
+        })
+    }
 }
 
 impl LogUpNode {
@@ -347,25 +369,37 @@ impl LogUpNode {
             transcript.read_felt_exts(2 * self.num_fs)?
         };
 
+        // Denominators come from the proof, so a zero one is rejected rather
+        // than unwrapped.
+        let invert = |d: E| {
+            Option::<E>::from(d.invert())
+                .ok_or_else(|| Error::InvalidProof("Zero LogUp denominator".to_string()))
+        };
         let lhs = if self.log2_t_size == 0 {
-            m_t_claims[0] * (m_t_claims[1] + gamma).invert().unwrap()
+            m_t_claims[0] * invert(m_t_claims[1] + gamma)?
         } else {
-            m_t_claims[0] * m_t_claims[1].invert().unwrap()
+            m_t_claims[0] * invert(m_t_claims[1])?
         };
         let rhs = if self.log2_f_size == 0 {
-            f_claims.iter().map(|f| (gamma + f).invert().unwrap()).sum()
+            f_claims
+                .iter()
+                .map(|f| invert(gamma + f))
+                .sum::<Result<E, _>>()?
         } else {
             f_claims
                 .iter()
                 .tuples()
-                .map(|(n, d)| *n * d.invert().unwrap())
-                .sum()
+                .map(|(n, d)| Ok(*n * invert(*d)?))
+                .sum::<Result<E, _>>()?
         };
-        (lhs == rhs)
-            .then_some((m_t_claims, f_claims))
-            .ok_or(Error::InvalidSumCheck(
-                "Unmatched LogUp final claims".to_string(),
-            ))
+        if lhs != rhs {
+            return Err(Error::InvalidSumCheck(SumCheckError::new(
+                SumCheckPhase::Node("Unmatched LogUp final claims".to_string()),
+                &lhs,
+                &rhs,
+            )));
+        }
+        Ok((m_t_claims, f_claims))
     }
 
     fn sum_check_relation<F, E, const IS_PROVING: bool>(
@@ -527,17 +561,21 @@ fn fractional_sums<F: Field, E: ExtensionField<F>>(
 pub mod test {
     use crate::{
         circuit::{
-            node::{input::InputNode, log_up::LogUpNode},
+            node::{input::InputNode, log_up::LogUpNode, EvalClaim},
             test::{run_circuit, TestData},
             Circuit,
         },
-        poly::box_dense_poly,
+        dev::fuzz_verifier,
+        poly::{box_dense_poly, MultilinearPoly},
+        prove_gkr,
+        transcript::StdRngTranscript,
         util::{
-            arithmetic::{ExtensionField, Field},
+            arithmetic::{ExtensionField, Field, PrimeField},
             chain,
-            dev::{rand_range, rand_vec},
+            dev::{rand_range, rand_vec, seeded_std_rng},
             Itertools, RngCore,
         },
+        verify_gkr, Error,
     };
     use goldilocks::{Goldilocks, GoldilocksExt2};
     use std::iter;
@@ -552,6 +590,43 @@ pub mod test {
         run_circuit::<Goldilocks, GoldilocksExt2>(log_up_circuit::<_, _, 3>);
     }
 
+    #[test]
+    fn zero_denominator() {
+        let mut rng = seeded_std_rng();
+        let (circuit, inputs, _) = log_up_circuit::<Goldilocks, GoldilocksExt2, 2>(2, &mut rng);
+        let values = circuit.evaluate(inputs);
+        let output_claims = circuit
+            .outputs()
+            .map(|idx| EvalClaim::new(vec![], values[idx].evaluate(&[])))
+            .collect_vec();
+        let proof = {
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+
+        // Zeroes all final claims of the LogUp node, so some denominator is zero
+        // whatever the table size.
+        let felt_size = <Goldilocks as PrimeField>::Repr::default().as_ref().len();
+        let mut proof = proof;
+        proof[..6 * <GoldilocksExt2 as ExtensionField<Goldilocks>>::DEGREE * felt_size].fill(0);
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        assert!(matches!(
+            verify_gkr(&circuit, &output_claims, &mut transcript),
+            Err(Error::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn fuzz() {
+        let mut rng = seeded_std_rng();
+        for log2_f_size in 1..4 {
+            let (circuit, inputs, _) =
+                log_up_circuit::<Goldilocks, GoldilocksExt2, 2>(log2_f_size, &mut rng);
+            fuzz_verifier(&circuit, &circuit.evaluate(inputs), 64, &mut rng).unwrap();
+        }
+    }
+
     fn log_up_circuit<F: Field, E: ExtensionField<F>, const N: usize>(
         log2_f_size: usize,
         mut rng: &mut impl RngCore,
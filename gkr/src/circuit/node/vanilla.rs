@@ -1,5 +1,8 @@
 use crate::{
-    circuit::node::{CombinedEvalClaim, EvalClaim, Node},
+    circuit::{
+        node::{CombinedEvalClaim, EvalClaim, Node},
+        NodeDescription,
+    },
     poly::{
         box_dense_poly, box_owned_dense_poly, eq_eval, repeated_dense_poly, BoxMultilinearPoly,
         BoxMultilinearPolyOwned, MultilinearPoly, PartialEqPoly,
@@ -10,7 +13,7 @@ use crate::{
     },
     transcript::{TranscriptRead, TranscriptWrite},
     util::{
-        arithmetic::{inner_product, ExtensionField, Field},
+        arithmetic::{inner_product, ExtensionField, Field, MixedFelt},
         chain,
         collection::{AdditiveVec, Hadamard},
         expression::Expression,
@@ -42,6 +45,10 @@ impl<F: Field, E: ExtensionField<F>> Node<F, E> for VanillaNode<F, E> {
         false
     }
 
+    fn input_arity(&self) -> usize {
+        self.input_arity
+    }
+
     fn log2_input_size(&self) -> usize {
         self.log2_sub_input_size + self.log2_reps
     }
@@ -146,12 +153,22 @@ impl<F: Field, E: ExtensionField<F>> Node<F, E> for VanillaNode<F, E> {
             input_r_xs.push((izip!(indices.iter().cloned(), input_r_x_is)).collect());
             eq_r_xs.push(self.eq_r_x(&r_xs[phase], &input_r_xs[phase]));
         }
-        if claim != self.sum_check_eval(&eq_r_gs, &eq_r_xs, &input_r_xs) {
-            return Err(err_unmatched_evaluation());
+        let expected = self.sum_check_eval(&eq_r_gs, &eq_r_xs, &input_r_xs);
+        if claim != expected {
+            return Err(err_unmatched_evaluation(&expected, &claim));
         }
 
         Ok(self.input_claims(&r_xs, &input_r_xs))
     }
+
+    fn description(&self) -> Option<NodeDescription<F>> {
+        Some(NodeDescription::Vanilla {
+            input_arity: self.input_arity,
+            log2_sub_input_size: self.log2_sub_input_size,
+            log2_reps: self.log2_reps,
+            gates: self.gates.clone(),
+        })
+    }
 }
 
 impl<F: Field, E: ExtensionField<F>> VanillaNode<F, E> {
@@ -162,12 +179,7 @@ impl<F: Field, E: ExtensionField<F>> VanillaNode<F, E> {
         num_reps: usize,
     ) -> Self {
         assert!(!gates.is_empty());
-        let inputs = Vec::from_iter(gates.iter().flat_map(|gate| {
-            chain![
-                gate.d_1.iter().map(|w| w.1),
-                gate.d_2.iter().flat_map(|w| [w.1, w.2])
-            ]
-        }));
+        let inputs = Vec::from_iter(gates.iter().flat_map(VanillaGate::wires));
         assert!(!inputs.iter().any(|(_, b)| *b >= 1 << log2_sub_input_size));
         assert_eq!(inputs.iter().map(|(i, _)| i).unique().count(), input_arity);
         assert!(num_reps != 0);
@@ -369,11 +381,11 @@ impl<F: Field, E: ExtensionField<F>> VanillaNode<F, E> {
             })
             .collect_vec();
         let evaluate = |expr: &Expression<F, Wire>, rep: usize| {
-            expr.evaluate_felt(&|(idx, b)| {
+            expr.evaluate_mixed(&|(idx, b)| {
                 let b = (rep << sub_size[idx]) + b;
                 match &data[idx] {
-                    SumCheckPoly::Base(poly) => E::from(poly[b]),
-                    SumCheckPoly::Extension(poly) => poly[b],
+                    SumCheckPoly::Base(poly) => MixedFelt::Base(poly[b]),
+                    SumCheckPoly::Extension(poly) => MixedFelt::Extension(poly[b]),
                     _ => unreachable!(),
                 }
             })
@@ -545,7 +557,8 @@ fn wiring_expressions<F: Field>(
 
 pub type Wire = (usize, usize);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VanillaGate<F> {
     d_0: Option<F>,
     d_1: Vec<(Option<F>, Wire)>,
@@ -575,6 +588,13 @@ impl<F> VanillaGate<F> {
         Self::new(Some(constant), Vec::new(), Vec::new())
     }
 
+    pub(crate) fn wires(&self) -> impl Iterator<Item = Wire> + '_ {
+        chain![
+            self.d_1.iter().map(|w| w.1),
+            self.d_2.iter().flat_map(|w| [w.1, w.2])
+        ]
+    }
+
     pub fn relay(w: Wire) -> Self {
         Self::new(None, vec![(None, w)], Vec::new())
     }
@@ -668,7 +688,7 @@ pub mod test {
                 vanilla::{VanillaGate, VanillaNode},
                 NodeExt,
             },
-            test::{run_circuit, TestData},
+            test::{run_circuit_over_fields, TestData},
             Circuit,
         },
         connect,
@@ -680,7 +700,6 @@ pub mod test {
             izip, Itertools, RngCore,
         },
     };
-    use goldilocks::{Goldilocks, GoldilocksExt2};
     use std::iter;
 
     impl<F: Field, E: ExtensionField<F>> VanillaNode<F, E> {
@@ -736,32 +755,32 @@ pub mod test {
 
     #[test]
     fn cyclo_mul() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(shift_circuit);
+        run_circuit_over_fields!(shift_circuit);
     }
 
     #[test]
     fn hadamard() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(hadamard_circuit);
+        run_circuit_over_fields!(hadamard_circuit);
     }
 
     #[test]
     fn grand_product() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(grand_product_circuit);
+        run_circuit_over_fields!(grand_product_circuit);
     }
 
     #[test]
     fn grand_sum() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(grand_sum_circuit);
+        run_circuit_over_fields!(grand_sum_circuit);
     }
 
     #[test]
     fn rand_linear() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(rand_linear_circuit);
+        run_circuit_over_fields!(rand_linear_circuit);
     }
 
     #[test]
     fn rand_dag() {
-        run_circuit::<Goldilocks, GoldilocksExt2>(rand_dag_circuit);
+        run_circuit_over_fields!(rand_dag_circuit);
     }
 
     fn shift_circuit<F: Field, E: ExtensionField<F>>(
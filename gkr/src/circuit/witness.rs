@@ -0,0 +1,294 @@
+use crate::{
+    circuit::{Circuit, NodeId},
+    poly::BoxMultilinearPoly,
+    util::{
+        arithmetic::{ExtensionField, Field},
+        izip_eq, Itertools,
+    },
+    Error,
+};
+use std::{fmt, iter, mem};
+
+// Dependencies of an input node with the function computing its value from
+// them.
+type Generator<'a, F, E> = (
+    Vec<usize>,
+    Box<
+        dyn Fn(Vec<&BoxMultilinearPoly<'a, F, E>>) -> BoxMultilinearPoly<'a, F, E>
+            + Send
+            + Sync
+            + 'a,
+    >,
+);
+
+/// Generates values of all nodes, where input nodes can either be provided by
+/// the caller or computed from values of other nodes by a registered
+/// generator (e.g. lookup inputs derived from intermediate values).
+pub struct WitnessGenerator<'a, 'b, F, E> {
+    circuit: &'a Circuit<F, E>,
+    generators: Vec<Option<Generator<'b, F, E>>>,
+}
+
+impl<F, E> fmt::Debug for WitnessGenerator<'_, '_, F, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WitnessGenerator")
+            .field(
+                "generators",
+                &self
+                    .generators
+                    .iter()
+                    .positions(Option::is_some)
+                    .collect_vec(),
+            )
+            .finish()
+    }
+}
+
+impl<'a, 'b, F: Field, E: ExtensionField<F>> WitnessGenerator<'a, 'b, F, E> {
+    pub fn new(circuit: &'a Circuit<F, E>) -> Self {
+        let generators = iter::repeat_with(|| None)
+            .take(circuit.nodes().len())
+            .collect();
+        Self {
+            circuit,
+            generators,
+        }
+    }
+
+    /// Registers `generator` to compute the value of input node `input` from
+    /// the values of `deps`, or returns [`Error::InvalidCircuit`] if `input`
+    /// isn't an input node, some of `deps` don't exist, or they depend on
+    /// `input` themselves.
+    pub fn generate(
+        &mut self,
+        input: NodeId,
+        deps: &[NodeId],
+        generator: impl Fn(Vec<&BoxMultilinearPoly<'b, F, E>>) -> BoxMultilinearPoly<'b, F, E>
+            + Send
+            + Sync
+            + 'b,
+    ) -> Result<&mut Self, Error> {
+        let num_nodes = self.circuit.nodes().len();
+        if input.0 >= num_nodes || !self.circuit.nodes()[input.0].is_input() {
+            return Err(Error::InvalidCircuit(format!(
+                "Generated node {} is not an input node",
+                input.0
+            )));
+        }
+        let deps = deps.iter().map(|dep| dep.0).collect_vec();
+        if let Some(dep) = deps.iter().find(|dep| **dep >= num_nodes) {
+            return Err(Error::InvalidCircuit(format!(
+                "Dependency {dep} of input node {} doesn't exist",
+                input.0
+            )));
+        }
+
+        // Circuits are acyclic and so are the registered generators, so a new
+        // cycle has to go through `input`.
+        let mut visited = vec![false; num_nodes];
+        let mut stack = deps.clone();
+        while let Some(idx) = stack.pop() {
+            if idx == input.0 {
+                return Err(Error::InvalidCircuit(format!(
+                    "Dependencies of input node {} depend on it",
+                    input.0
+                )));
+            }
+            if !mem::replace(&mut visited[idx], true) {
+                stack.extend(self.deps(idx));
+            }
+        }
+
+        self.generators[input.0] = Some((deps, Box::new(generator)));
+        Ok(self)
+    }
+
+    /// Returns values of all nodes given values of the input nodes without a
+    /// generator, in the order of [`Circuit::inputs`].
+    pub fn run(
+        &self,
+        inputs: Vec<BoxMultilinearPoly<'b, F, E>>,
+    ) -> Result<Vec<BoxMultilinearPoly<'b, F, E>>, Error> {
+        self.run_with_trace(inputs, |_, _| {})
+    }
+
+    /// Same as [`Self::run`] but calls `trace` with each node value as soon as
+    /// it's generated.
+    pub fn run_with_trace(
+        &self,
+        inputs: Vec<BoxMultilinearPoly<'b, F, E>>,
+        mut trace: impl FnMut(usize, &BoxMultilinearPoly<'b, F, E>),
+    ) -> Result<Vec<BoxMultilinearPoly<'b, F, E>>, Error> {
+        let circuit = self.circuit;
+        let mut values = Vec::from_iter(iter::repeat_with(|| None).take(circuit.nodes().len()));
+        let free_inputs = circuit
+            .inputs()
+            .filter(|idx| self.generators[*idx].is_none())
+            .collect_vec();
+        if free_inputs.len() != inputs.len() {
+            return Err(Error::InvalidCircuit(format!(
+                "Expected {} input values but got {}",
+                free_inputs.len(),
+                inputs.len()
+            )));
+        }
+        for (idx, input) in izip_eq!(&free_inputs, &inputs) {
+            let node = &circuit.nodes()[*idx];
+            if input.num_vars() != node.log2_output_size() {
+                return Err(Error::InvalidCircuit(format!(
+                    "Value of input node {idx} has {} variables but expected {}",
+                    input.num_vars(),
+                    node.log2_output_size()
+                )));
+            }
+        }
+        izip_eq!(free_inputs, inputs).for_each(|(idx, input)| {
+            trace(idx, &input);
+            values[idx] = Some(input);
+        });
+
+        for idx in self.schedule() {
+            if values[idx].is_some() {
+                continue;
+            }
+
+            let node = &circuit.nodes()[idx];
+            let _span =
+                tracing::trace_span!("generate_witness", node = idx, name = %node.name()).entered();
+            let inputs = self
+                .deps(idx)
+                .into_iter()
+                .map(|dep| values[dep].as_ref().unwrap())
+                .collect_vec();
+            let value = match &self.generators[idx] {
+                Some((_, generator)) => generator(inputs),
+                None => node.evaluate(inputs),
+            };
+            if value.len() != node.output_size() {
+                return Err(Error::InvalidCircuit(format!(
+                    "Generated value of node {idx} has size {} but expected {}",
+                    value.len(),
+                    node.output_size()
+                )));
+            }
+            trace(idx, &value);
+            values[idx] = Some(value);
+        }
+
+        Ok(values.into_iter().map(Option::unwrap).collect())
+    }
+
+    // Returns the nodes whose values `idx` is computed from.
+    fn deps(&self, idx: usize) -> Vec<usize> {
+        match &self.generators[idx] {
+            Some((deps, _)) => deps.clone(),
+            None => self.circuit.predec(idx).collect(),
+        }
+    }
+
+    // Returns all nodes ordered after their dependencies, by a depth-first
+    // search that `generate` keeps free of cycles.
+    fn schedule(&self) -> Vec<usize> {
+        let num_nodes = self.circuit.nodes().len();
+        let mut visited = vec![false; num_nodes];
+        let mut order = Vec::with_capacity(num_nodes);
+        for root in 0..num_nodes {
+            if mem::replace(&mut visited[root], true) {
+                continue;
+            }
+            let mut stack = vec![(root, self.deps(root).into_iter())];
+            while let Some((idx, deps)) = stack.last_mut() {
+                match deps.find(|dep| !visited[*dep]) {
+                    Some(dep) => {
+                        visited[dep] = true;
+                        stack.push((dep, self.deps(dep).into_iter()));
+                    }
+                    None => {
+                        order.push(*idx);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            node::{VanillaGate, VanillaNode},
+            CircuitBuilder, NodeId, WitnessGenerator,
+        },
+        dev::run_gkr_with_values,
+        poly::{box_dense_poly, MultilinearPoly},
+        util::{
+            arithmetic::Field,
+            dev::{rand_vec, seeded_std_rng},
+            Itertools,
+        },
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn witness_generator() {
+        let mut rng = seeded_std_rng();
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [x, y] = [(); 2].map(|_| builder.input(4));
+        let gates = vec![VanillaGate::mul((0, 0), (1, 0))];
+        let xy = builder
+            .vanilla(VanillaNode::new(2, 0, gates, 1 << 4), &[x, y])
+            .unwrap();
+        builder.output(xy);
+        let circuit = builder.build().unwrap();
+
+        let mut generator = WitnessGenerator::new(&circuit);
+        generator
+            .generate(y.id(), &[x.id()], |inputs| {
+                box_dense_poly(
+                    inputs[0]
+                        .to_dense()
+                        .iter()
+                        .map(|x| x.square())
+                        .collect_vec(),
+                )
+            })
+            .unwrap();
+        let x_values = rand_vec::<Goldilocks>(1 << 4, &mut rng);
+        let mut traced = Vec::new();
+        let values = generator
+            .run_with_trace(vec![box_dense_poly(x_values.clone())], |idx, _| {
+                traced.push(idx)
+            })
+            .unwrap();
+        assert_eq!(traced, [0, 1, 2]);
+        assert_eq!(
+            values[2].to_dense(),
+            x_values.iter().map(|x| x.square() * x).collect_vec()
+        );
+        run_gkr_with_values(&circuit, &values, &mut rng);
+
+        let mut generator = WitnessGenerator::new(&circuit);
+        for (input, dep) in [(y.id(), xy.id()), (xy.id(), x.id()), (y.id(), NodeId(3))] {
+            assert!(matches!(
+                generator.generate(input, &[dep], |inputs| box_dense_poly(inputs[0].to_dense())),
+                Err(Error::InvalidCircuit(_))
+            ));
+        }
+
+        let generator = WitnessGenerator::new(&circuit);
+        assert!(matches!(
+            generator.run(vec![box_dense_poly(x_values.clone())]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            generator.run(vec![
+                box_dense_poly(x_values.clone()),
+                box_dense_poly(x_values[..1 << 3].to_vec()),
+            ]),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+}
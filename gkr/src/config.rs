@@ -0,0 +1,207 @@
+use crate::{
+    circuit::{
+        node::{EvalClaim, Node},
+        Circuit,
+    },
+    poly::BoxMultilinearPoly,
+    prove_gkr,
+    transcript::TranscriptWrite,
+    util::{
+        arithmetic::{ExtensionField, Field},
+        parallel::set_min_parallel_chunk,
+    },
+    Error,
+};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::{
+    mem::size_of,
+    num::NonZeroUsize,
+    sync::{Arc, OnceLock},
+};
+
+/// Resource settings of the prover, for embedding it in services that manage
+/// their own threads and memory. The default runs in the current rayon pool
+/// without a memory budget.
+#[derive(Clone, Debug, Default)]
+pub struct ProverConfig {
+    num_threads: Option<NonZeroUsize>,
+    min_parallel_chunk: Option<NonZeroUsize>,
+    memory_budget: Option<usize>,
+    // Built on first install and shared by clones, reset by setters it
+    // depends on.
+    pool: Arc<OnceLock<ThreadPool>>,
+}
+
+impl ProverConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the prover in a dedicated pool of `num_threads` threads.
+    pub fn with_num_threads(mut self, num_threads: NonZeroUsize) -> Self {
+        self.num_threads = Some(num_threads);
+        self.pool = Default::default();
+        self
+    }
+
+    /// Makes [`parallelize`](crate::util::parallel::parallelize) split work
+    /// into chunks of at least `min_parallel_chunk` items, to trade
+    /// parallelism for less per-chunk overhead.
+    pub fn with_min_parallel_chunk(mut self, min_parallel_chunk: NonZeroUsize) -> Self {
+        self.min_parallel_chunk = Some(min_parallel_chunk);
+        self.pool = Default::default();
+        self
+    }
+
+    /// Rejects circuits whose [`estimate_memory`] exceeds `memory_budget`
+    /// bytes before proving, since the prover has no streaming strategy to
+    /// fall back to.
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self
+    }
+
+    pub fn num_threads(&self) -> Option<NonZeroUsize> {
+        self.num_threads
+    }
+
+    pub fn min_parallel_chunk(&self) -> Option<NonZeroUsize> {
+        self.min_parallel_chunk
+    }
+
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.memory_budget
+    }
+
+    /// Runs `f` in the pool described by `self`, so all parallel iterators
+    /// inside use it. The pool is built on the first call and reused after.
+    pub fn install<T: Send>(&self, f: impl FnOnce() -> T + Send) -> Result<T, Error> {
+        match self.pool()? {
+            Some(pool) => Ok(pool.install(f)),
+            None => Ok(f()),
+        }
+    }
+
+    fn pool(&self) -> Result<Option<&ThreadPool>, Error> {
+        if self.num_threads.is_none() && self.min_parallel_chunk.is_none() {
+            return Ok(None);
+        }
+        if self.pool.get().is_none() {
+            let min_parallel_chunk = self.min_parallel_chunk.map_or(1, NonZeroUsize::get);
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(self.num_threads.map_or(0, NonZeroUsize::get))
+                .start_handler(move |_| set_min_parallel_chunk(min_parallel_chunk))
+                .build()
+                .map_err(|err| Error::ThreadPool(err.to_string()))?;
+            // Another clone might have won the race, either pool works.
+            let _ = self.pool.set(pool);
+        }
+        Ok(self.pool.get())
+    }
+}
+
+/// Rough estimate in bytes of the prover's working memory beyond `values`: the
+/// inputs of the largest node folded into `E`, plus its eq polynomial.
+pub fn estimate_memory<F: Field, E: ExtensionField<F>>(circuit: &Circuit<F, E>) -> usize {
+    circuit
+        .topo_iter()
+        .filter(|(_, node)| !node.is_input())
+        .map(|(idx, node)| (circuit.predec(idx).count() + 1) * node.input_size() * size_of::<E>())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Runs [`prove_gkr`] with the settings of `config`.
+pub fn prove_gkr_with_config<F, E, T>(
+    config: &ProverConfig,
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut T,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: TranscriptWrite<F, E> + Send,
+{
+    if let Some(memory_budget) = config.memory_budget {
+        let estimate = estimate_memory(circuit);
+        if estimate > memory_budget {
+            return Err(Error::ExceededMemoryBudget(format!(
+                "Estimated {estimate} bytes but budget is {memory_budget}"
+            )));
+        }
+    }
+
+    config.install(|| prove_gkr(circuit, values, output_claims, transcript))?
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        config::{estimate_memory, prove_gkr_with_config, ProverConfig},
+        dev::grand_product_circuit,
+        poly::MultilinearPoly,
+        transcript::StdRngTranscript,
+        util::{dev::seeded_std_rng, parallel::parallelize},
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+    use rayon::current_num_threads;
+    use std::{mem::size_of, num::NonZeroUsize, sync::Mutex};
+
+    #[test]
+    fn prover_config() {
+        let config = ProverConfig::new().with_num_threads(NonZeroUsize::MIN);
+        assert_eq!(config.install(current_num_threads).unwrap(), 1);
+        let pool = config.pool().unwrap().unwrap() as *const _;
+        assert_eq!(config.clone().pool().unwrap().unwrap() as *const _, pool);
+
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(10, &mut seeded_std_rng());
+        let output_claims = vec![EvalClaim::new(vec![], values[10].evaluate(&[]))];
+        let [serial, parallel] = [config, ProverConfig::default()].map(|config| {
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr_with_config(&config, &circuit, &values, &output_claims, &mut transcript)
+                .unwrap();
+            transcript.into_proof()
+        });
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn min_parallel_chunk() {
+        let config = ProverConfig::new().with_min_parallel_chunk(NonZeroUsize::new(100).unwrap());
+        let chunk_lens = config
+            .install(|| {
+                let chunk_lens = Mutex::new(Vec::new());
+                parallelize(&mut [0; 1000], 1, |_, chunk| {
+                    chunk_lens.lock().unwrap().push(chunk.len())
+                });
+                chunk_lens.into_inner().unwrap()
+            })
+            .unwrap();
+        assert!(chunk_lens.iter().all(|len| *len >= 100));
+        assert_eq!(chunk_lens.iter().sum::<usize>(), 1000);
+    }
+
+    #[test]
+    fn memory_budget() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(10, &mut seeded_std_rng());
+        let output_claims = vec![EvalClaim::new(vec![], values[10].evaluate(&[]))];
+        let estimate = estimate_memory(&circuit);
+        assert_eq!(estimate, 2 * (1 << 10) * size_of::<GoldilocksExt2>());
+
+        let prove = |config: ProverConfig| {
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr_with_config(&config, &circuit, &values, &output_claims, &mut transcript)
+        };
+        assert!(prove(ProverConfig::new().with_memory_budget(estimate)).is_ok());
+        assert!(matches!(
+            prove(ProverConfig::new().with_memory_budget(estimate - 1)),
+            Err(Error::ExceededMemoryBudget(_))
+        ));
+    }
+}
@@ -1,16 +1,45 @@
 use crate::{
-    circuit::{node::EvalClaim, Circuit},
-    poly::{BoxMultilinearPoly, MultilinearPoly},
+    circuit::{
+        node::{EvalClaim, InputNode, NodeExt, VanillaGate, VanillaNode},
+        Circuit,
+    },
+    poly::{box_dense_poly, BoxMultilinearPoly, MultilinearPoly},
     prove_gkr,
     transcript::StdRngTranscript,
     util::{
         arithmetic::{ExtensionField, PrimeField},
+        chain,
         dev::rand_vec,
         izip_eq, Itertools, RngCore,
     },
     verify_gkr,
 };
 
+mod fuzz;
+mod mock;
+
+pub use fuzz::{fuzz_verifier, verify_mutated, Mutation};
+pub use mock::{MockProver, VerifyFailure};
+
+/// Binary tree of multiplications reducing `2^log2_input_size` random inputs
+/// to their product, with the evaluated values of all nodes.
+pub fn grand_product_circuit<F: PrimeField, E: ExtensionField<F>>(
+    log2_input_size: usize,
+    rng: &mut impl RngCore,
+) -> (Circuit<F, E>, Vec<BoxMultilinearPoly<'static, F, E>>) {
+    let gates = vec![VanillaGate::mul((0, 0), (0, 1))];
+    let nodes = chain![
+        [InputNode::new(log2_input_size, 1).boxed()],
+        (0..log2_input_size)
+            .rev()
+            .map(|idx| VanillaNode::new(1, 1, gates.clone(), 1 << idx).boxed())
+    ]
+    .collect_vec();
+    let circuit = Circuit::linear(nodes);
+    let values = circuit.evaluate(vec![box_dense_poly(rand_vec(1 << log2_input_size, rng))]);
+    (circuit, values)
+}
+
 pub fn run_gkr<F: PrimeField, E: ExtensionField<F>>(
     circuit: &Circuit<F, E>,
     inputs: &[BoxMultilinearPoly<F, E>],
@@ -0,0 +1,247 @@
+use crate::{
+    circuit::{node::EvalClaim, Circuit},
+    poly::{BoxMultilinearPoly, MultilinearPoly},
+    prove_gkr,
+    transcript::StdRngTranscript,
+    util::{
+        arithmetic::{ExtensionField, PrimeField},
+        dev::{rand_range, rand_vec},
+        izip_eq, Itertools, RngCore,
+    },
+    verify_gkr, Error,
+};
+use std::ops::Range;
+
+/// Structured mutation of a proof, addressing it in units of serialized base
+/// field elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    /// Flips bit `bit` of the felt at `idx`, which might leave it non-canonical.
+    FlipBit { idx: usize, bit: usize },
+    /// Replaces the felt at `idx` with zero, to hit inversions of claims.
+    Zero { idx: usize },
+    /// Keeps only the first `len` felts, as if the last rounds were dropped.
+    Truncate { len: usize },
+    /// Swaps the felts at `lhs` and `rhs`, as if openings were reordered.
+    Swap { lhs: usize, rhs: usize },
+}
+
+impl Mutation {
+    /// Returns a random mutation of a proof of `num_felts` felts, or `None` if
+    /// there is nothing to mutate.
+    pub fn random(num_felts: usize, felt_size: usize, mut rng: impl RngCore) -> Option<Self> {
+        if num_felts == 0 || felt_size == 0 {
+            return None;
+        }
+        let [idx, other] = [(); 2].map(|_| rand_range(0..num_felts, &mut rng));
+        Some(match rng.next_u32() % 4 {
+            0 => Self::FlipBit {
+                idx,
+                bit: rand_range(0..8 * felt_size, &mut rng),
+            },
+            1 => Self::Zero { idx },
+            2 => Self::Truncate { len: idx },
+            _ => Self::Swap {
+                lhs: idx,
+                rhs: other,
+            },
+        })
+    }
+
+    /// Decodes a mutation from raw fuzzer input, a tag byte followed by two
+    /// little-endian `u32` operands, or returns `None` if `bytes` is too short.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (tag, bytes) = bytes.split_first()?;
+        let operand = |offset: usize| -> Option<usize> {
+            let bytes = bytes.get(offset..offset + 4)?;
+            Some(u32::from_le_bytes(bytes.try_into().ok()?) as usize)
+        };
+        let (lhs, rhs) = (operand(0)?, operand(4)?);
+        Some(match tag % 4 {
+            0 => Self::FlipBit { idx: lhs, bit: rhs },
+            1 => Self::Zero { idx: lhs },
+            2 => Self::Truncate { len: lhs },
+            _ => Self::Swap { lhs, rhs },
+        })
+    }
+
+    /// Returns `proof` with the mutation applied, or `None` if it addresses
+    /// bytes past the end of `proof`.
+    pub fn apply(&self, proof: &[u8], felt_size: usize) -> Option<Vec<u8>> {
+        let mut proof = proof.to_vec();
+        let felt = |idx: usize| -> Option<Range<usize>> {
+            let start = idx.checked_mul(felt_size)?;
+            let end = start.checked_add(felt_size)?;
+            (end <= proof.len()).then_some(start..end)
+        };
+        match *self {
+            Self::FlipBit { idx, bit } => {
+                let felt = felt(idx)?;
+                if bit >= 8 * felt_size {
+                    return None;
+                }
+                proof[felt.start + bit / 8] ^= 1 << (bit % 8);
+            }
+            Self::Zero { idx } => {
+                let felt = felt(idx)?;
+                proof[felt].fill(0);
+            }
+            Self::Truncate { len } => proof.truncate(len.saturating_mul(felt_size)),
+            Self::Swap { lhs, rhs } => {
+                let (lhs, rhs) = (felt(lhs)?, felt(rhs)?);
+                let lhs_felt = proof[lhs.clone()].to_vec();
+                proof.copy_within(rhs.clone(), lhs.start);
+                proof[rhs].copy_from_slice(&lhs_felt);
+            }
+        }
+        Some(proof)
+    }
+}
+
+/// Verifies `proof` with `mutation` applied against `output_claims`, failing
+/// if the verifier accepts it with claims on inputs that hold on `values`.
+/// Mutations that don't apply or don't change `proof` are skipped, and a panic
+/// of the verifier is not caught.
+pub fn verify_mutated<F: PrimeField, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    proof: &[u8],
+    mutation: Mutation,
+) -> Result<(), String> {
+    let felt_size = F::Repr::default().as_ref().len();
+    let Some(mutated) = mutation.apply(proof, felt_size) else {
+        return Ok(());
+    };
+    if mutated == proof {
+        return Ok(());
+    }
+
+    let mut transcript = StdRngTranscript::from_proof(&mutated);
+    match verify_gkr(circuit, output_claims, &mut transcript) {
+        Ok(input_claims) if input_claims_hold(circuit, values, &input_claims) => {
+            Err(format!("Accepted proof with {mutation:?}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Proves `circuit` with `values`, then checks that [`verify_gkr`] rejects
+/// `num_mutations` random [`Mutation`]s of the proof, either by returning an
+/// error or claims on inputs that don't hold. Fails only if proving does, and
+/// panics on a mutation that isn't rejected or if the verifier panics.
+pub fn fuzz_verifier<F: PrimeField, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    num_mutations: usize,
+    mut rng: impl RngCore,
+) -> Result<(), Error> {
+    let output_claims = circuit
+        .outputs()
+        .map(|idx| {
+            let point = rand_vec(circuit.nodes()[idx].log2_output_size(), &mut rng);
+            let value = values[idx].evaluate(&point);
+            EvalClaim::new(point, value)
+        })
+        .collect_vec();
+
+    let proof = {
+        let mut transcript = StdRngTranscript::default();
+        prove_gkr(circuit, values, &output_claims, &mut transcript)?;
+        transcript.into_proof()
+    };
+
+    let felt_size = F::Repr::default().as_ref().len();
+    let num_felts = proof.len() / felt_size;
+    for _ in 0..num_mutations {
+        let Some(mutation) = Mutation::random(num_felts, felt_size, &mut rng) else {
+            break;
+        };
+        verify_mutated(circuit, values, &output_claims, &proof, mutation)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+    Ok(())
+}
+
+// Returns whether `input_claims` hold on `values`, which is left to the PCS
+// outside of the dev setup.
+fn input_claims_hold<F: PrimeField, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    input_claims: &[Vec<EvalClaim<E>>],
+) -> bool {
+    izip_eq!(circuit.inputs(), input_claims).all(|(input, claims)| {
+        claims
+            .iter()
+            .all(|claim| values[input].evaluate(claim.point()) == claim.value())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        dev::{
+            fuzz::{fuzz_verifier, Mutation},
+            grand_product_circuit,
+        },
+        poly::MultilinearPoly,
+        transcript::StdRngTranscript,
+        util::{arithmetic::Field, dev::seeded_std_rng},
+        verify_gkr, Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn mutation() {
+        let proof = (0..8).collect::<Vec<u8>>();
+        let apply = |mutation: Mutation| mutation.apply(&proof, 2);
+        assert_eq!(
+            apply(Mutation::FlipBit { idx: 1, bit: 9 }).unwrap(),
+            [0, 1, 2, 1, 4, 5, 6, 7]
+        );
+        assert_eq!(
+            apply(Mutation::Zero { idx: 2 }).unwrap(),
+            [0, 1, 2, 3, 0, 0, 6, 7]
+        );
+        assert_eq!(apply(Mutation::Truncate { len: 1 }).unwrap(), [0, 1]);
+        assert_eq!(
+            apply(Mutation::Swap { lhs: 0, rhs: 3 }).unwrap(),
+            [6, 7, 2, 3, 4, 5, 0, 1]
+        );
+
+        assert_eq!(apply(Mutation::FlipBit { idx: 1, bit: 16 }), None);
+        assert_eq!(apply(Mutation::Zero { idx: 4 }), None);
+        assert_eq!(apply(Mutation::Swap { lhs: 0, rhs: usize::MAX }), None);
+        assert_eq!(apply(Mutation::Truncate { len: usize::MAX }).unwrap(), proof);
+        assert_eq!(Mutation::random(0, 2, seeded_std_rng()), None);
+
+        assert_eq!(
+            Mutation::from_bytes(&[7, 1, 0, 0, 0, 2, 0, 0, 0]),
+            Some(Mutation::Swap { lhs: 1, rhs: 2 })
+        );
+        assert_eq!(Mutation::from_bytes(&[0; 8]), None);
+    }
+
+    #[test]
+    fn fuzz() {
+        let mut rng = seeded_std_rng();
+        let (circuit, values) = grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut rng);
+        fuzz_verifier(&circuit, &values, 256, &mut rng).unwrap();
+    }
+
+    #[test]
+    fn wrong_output_claims() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let claim = EvalClaim::new(vec![], values[4].evaluate(&[]));
+        let wrong_point = EvalClaim::new(vec![GoldilocksExt2::ONE], claim.value());
+        for output_claims in [vec![], vec![claim.clone(); 2], vec![wrong_point]] {
+            let mut transcript = StdRngTranscript::from_proof(&[]);
+            assert!(matches!(
+                verify_gkr(&circuit, &output_claims, &mut transcript),
+                Err(Error::InvalidProof(_))
+            ));
+        }
+    }
+}
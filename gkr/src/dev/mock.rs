@@ -0,0 +1,269 @@
+use crate::{
+    circuit::{Circuit, NodeDescription},
+    poly::{BoxMultilinearPoly, MultilinearPoly},
+    util::{
+        arithmetic::{ExtensionField, PrimeField},
+        Itertools,
+    },
+};
+use std::{collections::HashMap, fmt};
+
+/// Checks a witness directly over the hypercube without PCS or Fiat-Shamir,
+/// to locate the node and row that would make the GKR proof fail.
+#[derive(Debug)]
+pub struct MockProver<'a, 'b, F, E> {
+    circuit: &'a Circuit<F, E>,
+    values: Vec<BoxMultilinearPoly<'b, F, E>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyFailure {
+    pub node: usize,
+    pub row: usize,
+    pub description: String,
+}
+
+impl fmt::Display for VerifyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "node {} row {}: {}",
+            self.node, self.row, self.description
+        )
+    }
+}
+
+impl<'a, 'b, F: PrimeField, E: ExtensionField<F>> MockProver<'a, 'b, F, E> {
+    /// Generates the witness of all nodes from `inputs`.
+    pub fn run(circuit: &'a Circuit<F, E>, inputs: Vec<BoxMultilinearPoly<'b, F, E>>) -> Self {
+        let values = circuit.evaluate(inputs);
+        Self::with_values(circuit, values)
+    }
+
+    pub fn with_values(
+        circuit: &'a Circuit<F, E>,
+        values: Vec<BoxMultilinearPoly<'b, F, E>>,
+    ) -> Self {
+        assert_eq!(values.len(), circuit.nodes().len());
+        Self { circuit, values }
+    }
+
+    pub fn values(&self) -> &[BoxMultilinearPoly<'b, F, E>] {
+        &self.values
+    }
+
+    /// Returns all rows where a node's value disagrees with its inputs, or
+    /// where a lookup or constraint doesn't hold. Constraints are checked from
+    /// their description, so those with constants outside `F` are skipped.
+    pub fn verify(&self) -> Result<(), Vec<VerifyFailure>> {
+        let failures = (0..self.circuit.nodes().len())
+            .flat_map(|idx| self.verify_node(idx))
+            .collect_vec();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    pub fn assert_satisfied(&self) {
+        if let Err(failures) = self.verify() {
+            panic!(
+                "Unsatisfied circuit:\n{}",
+                failures.iter().map(VerifyFailure::to_string).join("\n")
+            );
+        }
+    }
+
+    fn verify_node(&self, idx: usize) -> Vec<VerifyFailure> {
+        let node = &self.circuit.nodes()[idx];
+        let inputs = self
+            .circuit
+            .predec(idx)
+            .map(|i| &self.values[i])
+            .collect_vec();
+        let failure = |row, description| VerifyFailure {
+            node: idx,
+            row,
+            description,
+        };
+
+        if self.values[idx].len() != node.output_size() {
+            let description = format!(
+                "value has size {} but node has output size {}",
+                self.values[idx].len(),
+                node.output_size()
+            );
+            return vec![failure(0, description)];
+        }
+        match node.description() {
+            Some(NodeDescription::Input { .. }) => Vec::new(),
+            Some(NodeDescription::LogUp { .. }) => verify_lookup(&inputs)
+                .into_iter()
+                .map(|(row, description)| failure(row, description))
+                .collect(),
+            Some(NodeDescription::Constraint { expression, .. }) => (0..node.input_size())
+                .filter_map(|row| {
+                    let value = expression.evaluate_felt(&|poly| inputs[poly][row]);
+                    (value != F::ZERO)
+                        .then(|| failure(row, format!("constraint evaluates to {value:?}")))
+                })
+                .collect(),
+            description => {
+                let expected = node.evaluate(inputs);
+                (0..expected.len())
+                    .filter(|row| expected[*row] != self.values[idx][*row])
+                    .map(|row| {
+                        let equation = match &description {
+                            Some(NodeDescription::Vanilla { gates, .. }) => {
+                                format!(" by {:?}", gates[row % gates.len()])
+                            }
+                            _ => String::new(),
+                        };
+                        let description = format!(
+                            "value {:?} but evaluates to {:?}{equation}",
+                            self.values[idx][row], expected[row]
+                        );
+                        failure(row, description)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+// Inputs are `[m, t, fs..]` as in `LogUpNode`.
+fn verify_lookup<F: PrimeField, E>(inputs: &[&BoxMultilinearPoly<F, E>]) -> Vec<(usize, String)> {
+    let (m, t, fs) = (inputs[0], inputs[1], &inputs[2..]);
+    let key = |value: F| value.to_repr().as_ref().to_vec();
+
+    let mut counts = HashMap::<_, (usize, u64)>::new();
+    (0..t.len()).rev().for_each(|row| {
+        counts.insert(key(t[row]), (row, 0));
+    });
+    let mut failures = Vec::new();
+    for (idx, f) in fs.iter().enumerate() {
+        for row in 0..f.len() {
+            match counts.get_mut(&key(f[row])) {
+                Some((_, count)) => *count += 1,
+                None => failures.push((row, format!("f_{idx} value {:?} not in table", f[row]))),
+            }
+        }
+    }
+
+    let mut multiplicities = HashMap::<_, F>::new();
+    (0..t.len()).for_each(|row| *multiplicities.entry(key(t[row])).or_default() += m[row]);
+    for (key, (row, count)) in counts.into_iter().sorted_by_key(|(_, (row, _))| *row) {
+        if multiplicities[&key] != F::from(count) {
+            failures.push((
+                row,
+                format!(
+                    "table value {:?} has multiplicity {:?} but is looked up {count} times",
+                    t[row], multiplicities[&key]
+                ),
+            ));
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::{
+            node::{ConstraintNode, VanillaGate, VanillaNode},
+            CircuitBuilder,
+        },
+        dev::{MockProver, VerifyFailure},
+        poly::box_dense_poly,
+        util::{
+            arithmetic::Field,
+            dev::{rand_vec, seeded_std_rng},
+            expression::Expression,
+        },
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn mock_prover() {
+        let mut rng = seeded_std_rng();
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [x, y, m, t, f] = [4, 4, 2, 2, 3].map(|log2_size| builder.input(log2_size));
+        let gates = vec![VanillaGate::mul((0, 0), (1, 0))];
+        let xy = builder
+            .vanilla(VanillaNode::new(2, 0, gates, 1 << 4), &[x, y])
+            .unwrap();
+        let log_up = builder.log_up(m, t, &[f]).unwrap();
+        builder.output(xy);
+        builder.output(log_up);
+        let circuit = builder.build().unwrap();
+
+        let m = [2, 0, 5, 1].map(Goldilocks::from).to_vec();
+        let t = [0, 1, 2, 3].map(Goldilocks::from).to_vec();
+        let f = [0, 2, 2, 3, 2, 0, 2, 2].map(Goldilocks::from).to_vec();
+        let inputs = |f: Vec<Goldilocks>, rng: &mut _| {
+            let [x, y] = [(); 2].map(|_| box_dense_poly(rand_vec(1 << 4, &mut *rng)));
+            vec![
+                x,
+                y,
+                box_dense_poly(m.clone()),
+                box_dense_poly(t.clone()),
+                box_dense_poly(f),
+            ]
+        };
+        MockProver::run(&circuit, inputs(f.clone(), &mut rng)).assert_satisfied();
+
+        let mut wrong_f = f.clone();
+        wrong_f[6] = Goldilocks::from(4);
+        let failures = MockProver::run(&circuit, inputs(wrong_f, &mut rng))
+            .verify()
+            .unwrap_err();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|failure| (failure.node, failure.row))
+                .collect::<Vec<_>>(),
+            [(6, 6), (6, 2)]
+        );
+
+        let mut values = circuit.evaluate(inputs(f, &mut rng));
+        let mut xy = values[5].to_dense();
+        xy[3] += Goldilocks::ONE;
+        values[5] = box_dense_poly(xy);
+        let failures = MockProver::with_values(&circuit, values)
+            .verify()
+            .unwrap_err();
+        assert!(matches!(
+            failures.as_slice(),
+            [VerifyFailure {
+                node: 5,
+                row: 3,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn constraint() {
+        let mut builder = CircuitBuilder::<Goldilocks, GoldilocksExt2>::new();
+        let [a, b, c] = [(); 3].map(|_| builder.input(2));
+        let [a_expr, b_expr, c_expr] = [0, 1, 2].map(Expression::poly);
+        let constraint = ConstraintNode::new(2, a_expr * b_expr - c_expr);
+        let out = builder.node(constraint, &[a, b, c]).unwrap();
+        builder.output(out);
+        let circuit = builder.build().unwrap();
+
+        let [a, b, c] = [[1, 2, 3, 4], [5, 6, 7, 8], [5, 12, 20, 32]]
+            .map(|values| box_dense_poly(values.map(Goldilocks::from).to_vec()));
+        let failures = MockProver::run(&circuit, vec![a, b, c])
+            .verify()
+            .unwrap_err();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|failure| (failure.node, failure.row))
+                .collect::<Vec<_>>(),
+            [(3, 2)]
+        );
+    }
+}
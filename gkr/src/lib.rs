@@ -3,17 +3,24 @@ use crate::{
         node::{CombinedEvalClaim, EvalClaim},
         Circuit,
     },
-    poly::{BoxMultilinearPoly, MultilinearPoly},
+    pcs::{MultilinearPcs, OpeningAccumulator},
+    poly::{box_dense_poly, BoxMultilinearPoly, MultilinearPoly},
+    sum_check::SumCheckError,
     transcript::{Transcript, TranscriptRead, TranscriptWrite},
     util::{
         arithmetic::{ExtensionField, Field},
-        Itertools,
+        izip, Itertools,
     },
 };
 use std::{io, mem::take};
 
+pub mod aggregate;
 pub mod circuit;
+pub mod config;
+pub mod metrics;
+pub mod pcs;
 pub mod poly;
+pub mod proof;
 pub mod sum_check;
 pub mod transcript;
 pub mod util;
@@ -23,17 +30,66 @@ pub use ff_ext;
 #[cfg(any(test, feature = "dev"))]
 pub mod dev;
 
+#[cfg(all(test, feature = "alloc-tracking"))]
+#[global_allocator]
+static ALLOCATOR: metrics::TrackingAllocator = metrics::TrackingAllocator;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Error {
-    InvalidSumCheck(String),
+    InvalidSumCheck(SumCheckError),
+    InvalidPolyExpr(String),
+    InvalidProof(String),
+    InvalidCircuit(String),
+    InvalidWitness(String),
+    InvalidPcsParam(String),
+    InvalidPcsOpen(String),
+    ExceededMemoryBudget(String),
+    ThreadPool(String),
     Transcript(io::ErrorKind, String),
 }
 
+impl Error {
+    fn at_node(self, node: usize) -> Self {
+        match self {
+            Self::InvalidSumCheck(err) => Self::InvalidSumCheck(err.at_node(node)),
+            err => err,
+        }
+    }
+}
+
+/// Absorbs `output_claims` into `transcript` and reduces them to claims on
+/// the inputs of `circuit`, returned in [`Circuit::inputs`] order.
 pub fn prove_gkr<F: Field, E: ExtensionField<F>>(
     circuit: &Circuit<F, E>,
     values: &[BoxMultilinearPoly<F, E>],
     output_claims: &[EvalClaim<E>],
     transcript: &mut impl TranscriptWrite<F, E>,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    prove_gkr_with_hook(circuit, values, output_claims, transcript, |_, _| {})
+}
+
+// Absorbs `output_claims` as [`prove_gkr`] does, then calls `hook` with the
+// index of each non-input node right after its claim reduction has been
+// written to `transcript`.
+pub(crate) fn prove_gkr_with_hook<F: Field, E: ExtensionField<F>, T: TranscriptWrite<F, E>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut T,
+    hook: impl FnMut(usize, &T),
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    absorb_output_claims(output_claims, transcript);
+    prove_claims(circuit, values, output_claims, transcript, hook)
+}
+
+// Reduces `output_claims` to claims on inputs, leaving the statement to be
+// absorbed by the caller.
+pub(crate) fn prove_claims<F: Field, E: ExtensionField<F>, T: TranscriptWrite<F, E>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut T,
+    mut hook: impl FnMut(usize, &T),
 ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
     circuit
         .topo_iter()
@@ -59,6 +115,7 @@ pub fn prove_gkr<F: Field, E: ExtensionField<F>>(
         let claim = combined_claim(take(&mut claims[idx]), transcript);
         let inputs = circuit.predec(idx).map(|idx| &values[idx]).collect();
         let sub_claims = node.prove_claim_reduction(claim, inputs, transcript)?;
+        hook(idx, transcript);
 
         izip_eq!(circuit.predec(idx), sub_claims)
             .for_each(|(idx, sub_claims)| claims[idx].extend(sub_claims));
@@ -76,8 +133,35 @@ pub fn verify_gkr<F: Field, E: ExtensionField<F>>(
     output_claims: &[EvalClaim<E>],
     transcript: &mut impl TranscriptRead<F, E>,
 ) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    absorb_output_claims(output_claims, transcript);
+    verify_claims(circuit, output_claims, transcript)
+}
+
+// Verifier counterpart of `prove_claims`.
+pub(crate) fn verify_claims<F: Field, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut impl TranscriptRead<F, E>,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    if output_claims.len() != circuit.outputs().count() {
+        return Err(Error::InvalidProof(format!(
+            "Expected {} output claims but got {}",
+            circuit.outputs().count(),
+            output_claims.len()
+        )));
+    }
+    if let Some((idx, claim)) = izip!(circuit.outputs(), output_claims)
+        .find(|(idx, claim)| claim.point().len() != circuit.nodes()[*idx].log2_output_size())
+    {
+        return Err(Error::InvalidProof(format!(
+            "Output claim on node {idx} has {} variables but expected {}",
+            claim.point().len(),
+            circuit.nodes()[idx].log2_output_size()
+        )));
+    }
+
     let mut claims = vec![Vec::new(); circuit.nodes().len()];
-    izip_eq!(circuit.outputs(), output_claims)
+    izip!(circuit.outputs(), output_claims)
         .for_each(|(idx, claim)| claims[idx] = vec![claim.clone()]);
 
     for (idx, node) in circuit.topo_iter().rev() {
@@ -86,19 +170,252 @@ pub fn verify_gkr<F: Field, E: ExtensionField<F>>(
         }
 
         let claim = combined_claim(take(&mut claims[idx]), transcript);
-        let sub_claims = node.verify_claim_reduction(claim, transcript)?;
+        let sub_claims = node
+            .verify_claim_reduction(claim, transcript)
+            .map_err(|err| err.at_node(idx))?;
 
-        izip_eq!(circuit.predec(idx), sub_claims)
+        if sub_claims.len() != circuit.predec(idx).count() {
+            return Err(Error::InvalidProof(format!(
+                "Node {idx} reduced to claims on {} inputs but has {}",
+                sub_claims.len(),
+                circuit.predec(idx).count()
+            )));
+        }
+        izip!(circuit.predec(idx), sub_claims)
             .for_each(|(idx, sub_claims)| claims[idx].extend(sub_claims));
     }
 
     let input_claims = Vec::from_iter(circuit.inputs().map(|idx| take(&mut claims[idx])));
 
-    assert!(!claims.iter().any(|claims| !claims.is_empty()));
+    if let Some(idx) = claims.iter().position(|claims| !claims.is_empty()) {
+        return Err(Error::InvalidProof(format!(
+            "Claims on node {idx} were left unreduced"
+        )));
+    }
+
+    Ok(input_claims)
+}
+
+/// Proves `circuit` like [`prove_gkr`], but binds `input_comms`, the
+/// commitments to the inputs in [`Circuit::inputs`] order, with
+/// [`absorb_statement`] first, and defers the claims on the inputs to
+/// `accumulator` to be opened with one batched PCS opening.
+pub fn prove_gkr_deferred<F, E, Pcs>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    input_comms: &[Pcs::Commitment],
+    accumulator: &mut OpeningAccumulator<Pcs::Commitment, E>,
+    transcript: &mut impl TranscriptWrite<F, E>,
+) -> Result<(), Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    check_input_comms(circuit, input_comms)?;
+
+    absorb_statement::<F, E, Pcs>(input_comms, output_claims, transcript);
+    let input_claims = prove_claims(circuit, values, output_claims, transcript, |_, _| {})?;
+    accumulator.push_input_claims(input_comms, input_claims)
+}
+
+/// Verifies a proof made by [`prove_gkr_deferred`], deferring the claims on
+/// the inputs of `circuit` to `accumulator` to be settled with one batched PCS
+/// verification.
+pub fn verify_gkr_deferred<F, E, Pcs>(
+    circuit: &Circuit<F, E>,
+    output_claims: &[EvalClaim<E>],
+    input_comms: &[Pcs::Commitment],
+    accumulator: &mut OpeningAccumulator<Pcs::Commitment, E>,
+    transcript: &mut impl TranscriptRead<F, E>,
+) -> Result<(), Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    check_input_comms(circuit, input_comms)?;
+
+    absorb_statement::<F, E, Pcs>(input_comms, output_claims, transcript);
+    let input_claims = verify_claims(circuit, output_claims, transcript)?;
+    accumulator.push_input_claims(input_comms, input_claims)
+}
+
+pub(crate) fn check_input_comms<F: Field, E: ExtensionField<F>, C>(
+    circuit: &Circuit<F, E>,
+    input_comms: &[C],
+) -> Result<(), Error> {
+    if input_comms.len() != circuit.inputs().count() {
+        return Err(Error::InvalidCircuit(format!(
+            "Expected commitments to {} inputs but got {}",
+            circuit.inputs().count(),
+            input_comms.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Proves `circuit` with public IO: the values of `public_inputs` and of all
+/// outputs are absorbed into `transcript` rather than committed, and output
+/// claims are made at points squeezed afterwards. Returns the claims on the
+/// private inputs, with the claims on public inputs left empty.
+pub fn prove_gkr_with_public_io<F: Field, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    public_inputs: &[usize],
+    transcript: &mut impl TranscriptWrite<F, E>,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    check_public_inputs(circuit, public_inputs.iter().copied())?;
 
+    let public_inputs = public_inputs
+        .iter()
+        .map(|idx| (*idx, values[*idx].to_dense()))
+        .collect_vec();
+    let outputs = circuit
+        .outputs()
+        .map(|idx| values[idx].to_dense())
+        .collect_vec();
+    let output_claims = public_io_output_claims(circuit, &public_inputs, &outputs, transcript)?;
+
+    let mut input_claims = prove_gkr(circuit, values, &output_claims, transcript)?;
+    izip!(circuit.inputs(), &mut input_claims)
+        .filter(|(idx, _)| public_inputs.iter().any(|(public, _)| public == idx))
+        .for_each(|(_, claims)| claims.clear());
+    Ok(input_claims)
+}
+
+/// Verifies a proof made by [`prove_gkr_with_public_io`] against the values
+/// of the public inputs and of all outputs, checking the claims on public
+/// inputs directly.
+pub fn verify_gkr_with_public_io<F: Field, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    public_inputs: &[(usize, Vec<F>)],
+    outputs: &[Vec<F>],
+    transcript: &mut impl TranscriptRead<F, E>,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error> {
+    let output_claims = public_io_output_claims(circuit, public_inputs, outputs, transcript)?;
+
+    let mut input_claims = verify_gkr(circuit, &output_claims, transcript)?;
+    for (idx, claims) in izip!(circuit.inputs(), &mut input_claims) {
+        let Some((_, values)) = public_inputs.iter().find(|(public, _)| *public == idx) else {
+            continue;
+        };
+        let poly = box_dense_poly::<F, E, _>(values.as_slice());
+        if claims
+            .drain(..)
+            .any(|claim| poly.evaluate(claim.point()) != claim.value())
+        {
+            return Err(Error::InvalidProof(format!(
+                "Unmatched evaluation of public input {idx}"
+            )));
+        }
+    }
     Ok(input_claims)
 }
 
+fn public_io_output_claims<F: Field, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    public_inputs: &[(usize, Vec<F>)],
+    outputs: &[Vec<F>],
+    transcript: &mut impl Transcript<F, E>,
+) -> Result<Vec<EvalClaim<E>>, Error> {
+    let err_size = |idx: usize, values: &[F]| {
+        Error::InvalidProof(format!(
+            "Public values of node {idx} have size {} but expected {}",
+            values.len(),
+            circuit.nodes()[idx].output_size()
+        ))
+    };
+    check_public_inputs(circuit, public_inputs.iter().map(|(idx, _)| *idx))?;
+    for (idx, values) in public_inputs {
+        if values.len() != circuit.nodes()[*idx].output_size() {
+            return Err(err_size(*idx, values));
+        }
+    }
+    if outputs.len() != circuit.outputs().count() {
+        return Err(Error::InvalidProof(format!(
+            "Expected {} outputs but got {}",
+            circuit.outputs().count(),
+            outputs.len()
+        )));
+    }
+    if let Some((idx, values)) = izip_eq!(circuit.outputs(), outputs)
+        .find(|(idx, values)| values.len() != circuit.nodes()[*idx].output_size())
+    {
+        return Err(err_size(idx, values));
+    }
+
+    public_inputs
+        .iter()
+        .for_each(|(_, values)| transcript.common_felts(values));
+    outputs
+        .iter()
+        .for_each(|values| transcript.common_felts(values));
+    Ok(izip_eq!(circuit.outputs(), outputs)
+        .map(|(idx, values)| {
+            let point = transcript.squeeze_challenges(circuit.nodes()[idx].log2_output_size());
+            let value = box_dense_poly::<F, E, _>(values.as_slice()).evaluate(&point);
+            EvalClaim::new(point, value)
+        })
+        .collect())
+}
+
+// Checks that each of `public_inputs` is an input node given only once.
+fn check_public_inputs<F: Field, E: ExtensionField<F>>(
+    circuit: &Circuit<F, E>,
+    public_inputs: impl IntoIterator<Item = usize>,
+) -> Result<(), Error> {
+    let mut seen = Vec::new();
+    for idx in public_inputs {
+        if !circuit.inputs().contains(&idx) {
+            return Err(Error::InvalidCircuit(format!(
+                "Public input {idx} is not an input node"
+            )));
+        }
+        if seen.contains(&idx) {
+            return Err(Error::InvalidCircuit(format!(
+                "Public input {idx} is given more than once"
+            )));
+        }
+        seen.push(idx);
+    }
+    Ok(())
+}
+
+/// Absorbs the statement of a proof into `transcript`, the commitments to the
+/// inputs of a circuit followed by the points and values of its output claims,
+/// so every challenge squeezed afterwards is bound to them. Every entry point
+/// proving claims on committed inputs calls it exactly once before reducing
+/// any claim, while [`prove_gkr`] and [`verify_gkr`] absorb only the output
+/// claims.
+pub fn absorb_statement<F, E, Pcs>(
+    input_comms: &[Pcs::Commitment],
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut impl Transcript<F, E>,
+) where
+    F: Field,
+    E: ExtensionField<F>,
+    Pcs: MultilinearPcs<F, E>,
+{
+    for comm in input_comms {
+        Pcs::absorb_commitment(comm, transcript);
+    }
+    absorb_output_claims(output_claims, transcript);
+}
+
+fn absorb_output_claims<F: Field, E: ExtensionField<F>>(
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut impl Transcript<F, E>,
+) {
+    for claim in output_claims {
+        for felt in claim.point() {
+            transcript.common_felts(felt.as_bases());
+        }
+        transcript.common_felts(claim.value().as_bases());
+    }
+}
+
 fn combined_claim<F: Field, E: ExtensionField<F>>(
     claims: Vec<EvalClaim<E>>,
     transcript: &mut impl Transcript<F, E>,
@@ -110,3 +427,155 @@ fn combined_claim<F: Field, E: ExtensionField<F>>(
     };
     CombinedEvalClaim::new(claims, alphas)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        dev::grand_product_circuit,
+        pcs::{MultilinearPcs, OpeningAccumulator, TrivialPcs},
+        poly::MultilinearPoly,
+        prove_gkr, prove_gkr_deferred, prove_gkr_with_public_io,
+        sum_check::SumCheckPhase,
+        transcript::StdRngTranscript,
+        util::{arithmetic::Field, dev::seeded_std_rng},
+        verify_gkr, verify_gkr_deferred, verify_gkr_with_public_io, Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn verify_deferred() {
+        type Pcs = TrivialPcs<Goldilocks, GoldilocksExt2>;
+
+        let mut rng = seeded_std_rng();
+        let (circuit, values) = grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut rng);
+        let (pp, vp) = Pcs::trim(&Pcs::setup(4, &mut rng).unwrap(), 4).unwrap();
+        let input_comms = vec![Pcs::commit(&pp, &*values[0]).unwrap()];
+        let output_claims = vec![EvalClaim::new(vec![], values[4].evaluate(&[]))];
+        let proof = {
+            let mut accumulator = OpeningAccumulator::new();
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr_deferred::<_, _, Pcs>(
+                &circuit,
+                &values,
+                &output_claims,
+                &input_comms,
+                &mut accumulator,
+                &mut transcript,
+            )
+            .unwrap();
+            accumulator
+                .open::<_, Pcs>(&pp, |_| Some(&*values[0]), &mut transcript)
+                .unwrap();
+            transcript.into_proof()
+        };
+
+        let verify = |input_comms: &[Vec<Goldilocks>]| {
+            let mut accumulator = OpeningAccumulator::new();
+            let mut transcript = StdRngTranscript::from_proof(&proof);
+            verify_gkr_deferred::<_, _, Pcs>(
+                &circuit,
+                &output_claims,
+                input_comms,
+                &mut accumulator,
+                &mut transcript,
+            )?;
+            accumulator.settle::<_, Pcs>(&vp, &mut transcript)
+        };
+        assert_eq!(verify(&input_comms), Ok(()));
+
+        // Commitments are absorbed first, so a tampered one already changes
+        // the challenges of the GKR proof.
+        let mut wrong_comm = input_comms[0].clone();
+        wrong_comm[0] += Goldilocks::ONE;
+        assert!(matches!(
+            verify(&[wrong_comm]),
+            Err(Error::InvalidSumCheck(_))
+        ));
+        assert!(matches!(verify(&[]), Err(Error::InvalidCircuit(_))));
+    }
+
+    #[test]
+    fn sum_check_error() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let proof = {
+            let output_claims = vec![EvalClaim::new(vec![], values[4].evaluate(&[]))];
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+
+        let wrong_output_claims = vec![EvalClaim::new(vec![], GoldilocksExt2::ONE)];
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        let Err(Error::InvalidSumCheck(err)) =
+            verify_gkr(&circuit, &wrong_output_claims, &mut transcript)
+        else {
+            panic!("expected sum-check error");
+        };
+        assert_eq!(err.node(), Some(4));
+        assert_eq!(err.phase(), &SumCheckPhase::FinalEvaluation);
+        assert_ne!(err.expected(), err.got());
+    }
+
+    #[test]
+    fn public_io() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let proof = {
+            let mut transcript = StdRngTranscript::default();
+            let input_claims =
+                prove_gkr_with_public_io(&circuit, &values, &[0], &mut transcript).unwrap();
+            assert!(input_claims.iter().all(Vec::is_empty));
+            transcript.into_proof()
+        };
+
+        let verify = |input: Vec<_>, output: Vec<_>| {
+            let mut transcript = StdRngTranscript::from_proof(&proof);
+            verify_gkr_with_public_io(&circuit, &[(0, input)], &[output], &mut transcript)
+        };
+        let (input, output) = (values[0].to_dense(), values[4].to_dense());
+        assert_eq!(verify(input.clone(), output.clone()), Ok(vec![vec![]]));
+
+        let mut wrong_input = input.clone();
+        wrong_input[0] += Goldilocks::ONE;
+        assert!(matches!(
+            verify(wrong_input, output.clone()),
+            Err(Error::InvalidProof(_))
+        ));
+        assert!(verify(input.clone(), vec![output[0] + Goldilocks::ONE]).is_err());
+
+        assert!(matches!(
+            verify(input[1..].to_vec(), output.clone()),
+            Err(Error::InvalidProof(_))
+        ));
+        assert!(matches!(
+            verify(input.clone(), [output.clone(), output.clone()].concat()),
+            Err(Error::InvalidProof(_))
+        ));
+        let verify_io = |public_inputs: &[(usize, Vec<_>)], outputs: &[Vec<_>]| {
+            let mut transcript = StdRngTranscript::from_proof(&proof);
+            verify_gkr_with_public_io(&circuit, public_inputs, outputs, &mut transcript)
+        };
+        assert!(matches!(
+            verify_io(&[(0, input.clone())], &[]),
+            Err(Error::InvalidProof(_))
+        ));
+        assert!(matches!(
+            verify_io(&[(1, output.clone())], &[output.clone()]),
+            Err(Error::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            verify_io(&[(0, input.clone()), (0, input)], &[output]),
+            Err(Error::InvalidCircuit(_))
+        ));
+
+        for public_inputs in [&[5][..], &[1], &[0, 0]] {
+            let mut transcript = StdRngTranscript::default();
+            assert!(matches!(
+                prove_gkr_with_public_io(&circuit, &values, public_inputs, &mut transcript),
+                Err(Error::InvalidCircuit(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    circuit::{
+        node::{EvalClaim, Node},
+        Circuit, NodeDescription,
+    },
+    poly::BoxMultilinearPoly,
+    prove_gkr_with_hook,
+    transcript::{TranscriptProof, TranscriptWrite},
+    util::arithmetic::{ExtensionField, Field},
+    Error,
+};
+use std::time::Instant;
+
+#[cfg(feature = "alloc-tracking")]
+pub use alloc::TrackingAllocator;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Metric {
+    /// Number of evaluations of the node's input hypercube.
+    HypercubeSize,
+    /// Number of lookups into tables, for lookup nodes.
+    Lookups,
+    /// Bytes the node appends to the proof.
+    ProofBytes,
+    /// Wall-clock time of the node's claim reduction.
+    Nanos,
+    /// Peak heap bytes during the node's claim reduction, only recorded with
+    /// feature `alloc-tracking` and `TrackingAllocator` as global allocator.
+    PeakBytes,
+}
+
+/// Sink receiving per-node counters from [`prove_gkr_with_metrics`].
+pub trait Metrics {
+    fn record(&mut self, node: usize, name: &str, metric: Metric, value: u64);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetricRecord {
+    pub node: usize,
+    pub name: String,
+    pub metric: Metric,
+    pub value: u64,
+}
+
+impl Metrics for Vec<MetricRecord> {
+    fn record(&mut self, node: usize, name: &str, metric: Metric, value: u64) {
+        self.push(MetricRecord {
+            node,
+            name: name.to_string(),
+            metric,
+            value,
+        });
+    }
+}
+
+/// Runs [`prove_gkr`](crate::prove_gkr) and reports counters of each proven
+/// node to `metrics` right after its claim reduction.
+pub fn prove_gkr_with_metrics<F, E, T>(
+    circuit: &Circuit<F, E>,
+    values: &[BoxMultilinearPoly<F, E>],
+    output_claims: &[EvalClaim<E>],
+    transcript: &mut T,
+    metrics: &mut impl Metrics,
+) -> Result<Vec<Vec<EvalClaim<E>>>, Error>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: TranscriptWrite<F, E> + TranscriptProof,
+{
+    let mut offset = transcript.proof().len();
+    let mut start = Instant::now();
+    #[cfg(feature = "alloc-tracking")]
+    TrackingAllocator::reset_peak();
+    prove_gkr_with_hook(
+        circuit,
+        values,
+        output_claims,
+        transcript,
+        |idx, transcript| {
+            let elapsed = start.elapsed();
+            let node = &circuit.nodes()[idx];
+            let name = node.name();
+            let proof_bytes = transcript.proof().len() - offset;
+
+            metrics.record(idx, &name, Metric::HypercubeSize, node.input_size() as u64);
+            if let Some(NodeDescription::LogUp {
+                log2_f_size,
+                num_fs,
+                ..
+            }) = node.description()
+            {
+                metrics.record(idx, &name, Metric::Lookups, (num_fs << log2_f_size) as u64);
+            }
+            metrics.record(idx, &name, Metric::ProofBytes, proof_bytes as u64);
+            metrics.record(idx, &name, Metric::Nanos, elapsed.as_nanos() as u64);
+            #[cfg(feature = "alloc-tracking")]
+            {
+                let peak_bytes = TrackingAllocator::peak() as u64;
+                metrics.record(idx, &name, Metric::PeakBytes, peak_bytes);
+                TrackingAllocator::reset_peak();
+            }
+
+            offset += proof_bytes;
+            start = Instant::now();
+        },
+    )
+}
+
+#[cfg(feature = "alloc-tracking")]
+mod alloc {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// [`System`] allocator counting live heap bytes, for
+    /// [`Metric::PeakBytes`](super::Metric::PeakBytes). Install it in the
+    /// binary with `#[global_allocator]`.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct TrackingAllocator;
+
+    impl TrackingAllocator {
+        pub fn allocated() -> usize {
+            ALLOCATED.load(Relaxed)
+        }
+
+        pub fn peak() -> usize {
+            PEAK.load(Relaxed)
+        }
+
+        /// Starts a new phase, with the peak lowered to the bytes allocated
+        /// now.
+        pub fn reset_peak() {
+            PEAK.store(Self::allocated(), Relaxed);
+        }
+
+        fn grow(size: usize) {
+            let allocated = ALLOCATED.fetch_add(size, Relaxed) + size;
+            PEAK.fetch_max(allocated, Relaxed);
+        }
+
+        fn shrink(size: usize) {
+            ALLOCATED.fetch_sub(size, Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                if new_size > layout.size() {
+                    Self::grow(new_size - layout.size());
+                } else {
+                    Self::shrink(layout.size() - new_size);
+                }
+            }
+            new_ptr
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        dev::grand_product_circuit,
+        metrics::{prove_gkr_with_metrics, Metric, MetricRecord},
+        poly::MultilinearPoly,
+        transcript::StdRngTranscript,
+        util::{dev::seeded_std_rng, Itertools},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn metrics() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let output_claims = vec![EvalClaim::new(vec![], values[4].evaluate(&[]))];
+        let mut transcript = StdRngTranscript::default();
+        let mut metrics = Vec::<MetricRecord>::new();
+        prove_gkr_with_metrics(
+            &circuit,
+            &values,
+            &output_claims,
+            &mut transcript,
+            &mut metrics,
+        )
+        .unwrap();
+
+        let values_of = |metric| {
+            metrics
+                .iter()
+                .filter(|record| record.metric == metric)
+                .map(|record| (record.node, record.value))
+                .collect_vec()
+        };
+        assert_eq!(
+            values_of(Metric::HypercubeSize),
+            [(4, 2), (3, 4), (2, 8), (1, 16)]
+        );
+        assert!(values_of(Metric::Lookups).is_empty());
+        assert_eq!(
+            values_of(Metric::ProofBytes)
+                .iter()
+                .map(|(_, bytes)| bytes)
+                .sum::<u64>(),
+            transcript.into_proof().len() as u64
+        );
+        assert!(metrics.iter().all(|record| record.name == "VanillaNode"));
+        // The peak is process-wide and reset by other tests running in
+        // parallel, so only check that it's recorded for each node.
+        if cfg!(feature = "alloc-tracking") {
+            assert_eq!(
+                values_of(Metric::PeakBytes)
+                    .iter()
+                    .map(|(node, _)| *node)
+                    .collect_vec(),
+                [4, 3, 2, 1]
+            );
+        }
+    }
+}
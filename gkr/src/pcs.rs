@@ -0,0 +1,187 @@
+use crate::{
+    poly::{DensePolynomial, DynMultilinearPoly},
+    transcript::{Transcript, TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{ExtensionField, Field},
+        RngCore,
+    },
+    Error,
+};
+use std::fmt::Debug;
+
+mod accumulator;
+mod trivial;
+
+pub use accumulator::OpeningAccumulator;
+pub use trivial::TrivialPcs;
+
+/// Claimed evaluation of `polys[poly]` at `points[point]` in batched openings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Evaluation<E> {
+    poly: usize,
+    point: usize,
+    value: E,
+}
+
+impl<E> Evaluation<E> {
+    pub fn new(poly: usize, point: usize, value: E) -> Self {
+        Self { poly, point, value }
+    }
+
+    pub fn poly(&self) -> usize {
+        self.poly
+    }
+
+    pub fn point(&self) -> usize {
+        self.point
+    }
+
+    pub fn value(&self) -> &E {
+        &self.value
+    }
+
+    /// Returns the commitment and point this evaluation refers to, or
+    /// [`Error::InvalidPcsOpen`] if either index is out of range.
+    pub fn resolve<'a, C>(
+        &self,
+        comms: &'a [C],
+        points: &'a [Vec<E>],
+    ) -> Result<(&'a C, &'a [E]), Error> {
+        let comm = comms.get(self.poly).ok_or_else(|| {
+            Error::InvalidPcsOpen(format!(
+                "Evaluation of polynomial {} out of {}",
+                self.poly,
+                comms.len()
+            ))
+        })?;
+        let point = points.get(self.point).ok_or_else(|| {
+            Error::InvalidPcsOpen(format!(
+                "Evaluation at point {} out of {}",
+                self.point,
+                points.len()
+            ))
+        })?;
+        Ok((comm, point.as_slice()))
+    }
+}
+
+/// Multilinear polynomial commitment scheme committing to polynomials over
+/// `F` and opening them at points over `E`.
+///
+/// The default batched methods open each evaluation separately, backends
+/// with native batching are expected to override them. Likewise the default
+/// [`MultilinearPcs::commit_chunks`] buffers the whole polynomial.
+pub trait MultilinearPcs<F: Field, E: ExtensionField<F>>: Clone + Debug {
+    type Param: Clone + Debug;
+    type ProverParam: Clone + Debug;
+    type VerifierParam: Clone + Debug;
+    type Commitment: Clone + Debug + PartialEq;
+
+    fn setup(max_num_vars: usize, rng: impl RngCore) -> Result<Self::Param, Error>;
+
+    fn trim(
+        param: &Self::Param,
+        num_vars: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error>;
+
+    fn commit(
+        pp: &Self::ProverParam,
+        poly: &DynMultilinearPoly<F, E>,
+    ) -> Result<Self::Commitment, Error>;
+
+    /// Commits to the polynomial with `num_vars` variables whose evaluations
+    /// are yielded in order by `chunks`, so hash-based backends can commit
+    /// without keeping all evaluations resident.
+    ///
+    /// Streaming is opt-in: the default implementation buffers all `2^num_vars`
+    /// evaluations and calls [`MultilinearPcs::commit`], so its peak memory is
+    /// the same as committing directly. No backend in this crate overrides it
+    /// yet, a hash-based one would hash each chunk as it arrives.
+    fn commit_chunks(
+        pp: &Self::ProverParam,
+        num_vars: usize,
+        chunks: impl IntoIterator<Item = Vec<F>>,
+    ) -> Result<Self::Commitment, Error> {
+        let mut evals = Vec::with_capacity(1 << num_vars);
+        for chunk in chunks {
+            if evals.len() + chunk.len() > 1 << num_vars {
+                return Err(Error::InvalidPcsParam(format!(
+                    "Expected {} evaluations but got more",
+                    1 << num_vars
+                )));
+            }
+            evals.extend(chunk);
+        }
+        if evals.len() != 1 << num_vars {
+            return Err(Error::InvalidPcsParam(format!(
+                "Expected {} evaluations but got {}",
+                1 << num_vars,
+                evals.len()
+            )));
+        }
+        Self::commit(pp, &DensePolynomial::new(evals))
+    }
+
+    /// Absorbs `comm` into `transcript`, so challenges squeezed afterwards
+    /// depend on the committed polynomial.
+    fn absorb_commitment(comm: &Self::Commitment, transcript: &mut impl Transcript<F, E>);
+
+    fn batch_commit(
+        pp: &Self::ProverParam,
+        polys: &[&DynMultilinearPoly<F, E>],
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        polys.iter().map(|poly| Self::commit(pp, *poly)).collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &DynMultilinearPoly<F, E>,
+        comm: &Self::Commitment,
+        point: &[E],
+        eval: &E,
+        transcript: &mut impl TranscriptWrite<F, E>,
+    ) -> Result<(), Error>;
+
+    fn batch_open(
+        pp: &Self::ProverParam,
+        polys: &[&DynMultilinearPoly<F, E>],
+        comms: &[Self::Commitment],
+        points: &[Vec<E>],
+        evals: &[Evaluation<E>],
+        transcript: &mut impl TranscriptWrite<F, E>,
+    ) -> Result<(), Error> {
+        if polys.len() != comms.len() {
+            return Err(Error::InvalidPcsOpen(format!(
+                "Expected {} polynomials but got {}",
+                comms.len(),
+                polys.len()
+            )));
+        }
+
+        evals.iter().try_for_each(|eval| {
+            let (comm, point) = eval.resolve(comms, points)?;
+            Self::open(pp, polys[eval.poly], comm, point, &eval.value, transcript)
+        })
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &[E],
+        eval: &E,
+        transcript: &mut impl TranscriptRead<F, E>,
+    ) -> Result<(), Error>;
+
+    fn batch_verify(
+        vp: &Self::VerifierParam,
+        comms: &[Self::Commitment],
+        points: &[Vec<E>],
+        evals: &[Evaluation<E>],
+        transcript: &mut impl TranscriptRead<F, E>,
+    ) -> Result<(), Error> {
+        evals.iter().try_for_each(|eval| {
+            let (comm, point) = eval.resolve(comms, points)?;
+            Self::verify(vp, comm, point, &eval.value, transcript)
+        })
+    }
+}
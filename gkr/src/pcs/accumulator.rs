@@ -0,0 +1,184 @@
+use crate::{
+    circuit::node::EvalClaim,
+    pcs::{Evaluation, MultilinearPcs},
+    poly::DynMultilinearPoly,
+    transcript::{TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{ExtensionField, Field},
+        izip,
+    },
+    Error,
+};
+
+/// Opening claims collected during verification, settled with a single
+/// [`MultilinearPcs::batch_verify`] at the end.
+///
+/// Equal commitments and equal points are deduplicated so backends with
+/// native batching can share work across claims on the same polynomial.
+#[derive(Clone, Debug)]
+pub struct OpeningAccumulator<C, E> {
+    comms: Vec<C>,
+    points: Vec<Vec<E>>,
+    evals: Vec<Evaluation<E>>,
+}
+
+impl<C, E> Default for OpeningAccumulator<C, E> {
+    fn default() -> Self {
+        Self {
+            comms: Vec::new(),
+            points: Vec::new(),
+            evals: Vec::new(),
+        }
+    }
+}
+
+impl<C: PartialEq, E: PartialEq> OpeningAccumulator<C, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, comm: C, point: Vec<E>, value: E) {
+        let poly = position_or_push(&mut self.comms, comm);
+        let point = position_or_push(&mut self.points, point);
+        self.evals.push(Evaluation::new(poly, point, value));
+    }
+
+    /// Pushes the claims on each input of a circuit, as returned by
+    /// [`verify_gkr`](crate::verify_gkr), with `input_comms` the commitments
+    /// to the inputs in the same order.
+    pub fn push_input_claims(
+        &mut self,
+        input_comms: &[C],
+        input_claims: Vec<Vec<EvalClaim<E>>>,
+    ) -> Result<(), Error>
+    where
+        C: Clone,
+        E: Copy,
+    {
+        if input_comms.len() != input_claims.len() {
+            return Err(Error::InvalidCircuit(format!(
+                "Expected commitments to {} inputs but got {}",
+                input_claims.len(),
+                input_comms.len()
+            )));
+        }
+        for (comm, claims) in izip!(input_comms, input_claims) {
+            for claim in claims {
+                self.push(comm.clone(), claim.point().to_vec(), claim.value());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.evals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.evals.is_empty()
+    }
+
+    pub fn settle<F, Pcs>(
+        self,
+        vp: &Pcs::VerifierParam,
+        transcript: &mut impl TranscriptRead<F, E>,
+    ) -> Result<(), Error>
+    where
+        F: Field,
+        E: ExtensionField<F>,
+        Pcs: MultilinearPcs<F, E, Commitment = C>,
+    {
+        Pcs::batch_verify(vp, &self.comms, &self.points, &self.evals, transcript)
+    }
+
+    /// Prover counterpart of [`OpeningAccumulator::settle`], where `poly`
+    /// returns the committed polynomial of each accumulated commitment, or
+    /// `None` to fail with [`Error::InvalidPcsOpen`].
+    pub fn open<'a, F, Pcs>(
+        self,
+        pp: &Pcs::ProverParam,
+        poly: impl Fn(&C) -> Option<&'a DynMultilinearPoly<'a, F, E>>,
+        transcript: &mut impl TranscriptWrite<F, E>,
+    ) -> Result<(), Error>
+    where
+        F: Field,
+        E: ExtensionField<F>,
+        Pcs: MultilinearPcs<F, E, Commitment = C>,
+    {
+        let polys = self
+            .comms
+            .iter()
+            .map(|comm| {
+                poly(comm).ok_or_else(|| {
+                    Error::InvalidPcsOpen("Missing polynomial of a commitment".to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Pcs::batch_open(
+            pp,
+            &polys,
+            &self.comms,
+            &self.points,
+            &self.evals,
+            transcript,
+        )
+    }
+}
+
+fn position_or_push<T: PartialEq>(values: &mut Vec<T>, value: T) -> usize {
+    values.iter().position(|v| *v == value).unwrap_or_else(|| {
+        values.push(value);
+        values.len() - 1
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{MultilinearPcs, OpeningAccumulator, TrivialPcs},
+        poly::{box_dense_poly, BoxMultilinearPoly},
+        transcript::StdRngTranscript,
+        util::{
+            arithmetic::Field,
+            dev::{rand_vec, seeded_std_rng},
+        },
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    type Pcs = TrivialPcs<Goldilocks, GoldilocksExt2>;
+
+    #[test]
+    fn opening_accumulator() {
+        let mut rng = seeded_std_rng();
+        let (pp, vp) = Pcs::trim(&Pcs::setup(4, &mut rng).unwrap(), 4).unwrap();
+        let poly: BoxMultilinearPoly<Goldilocks, GoldilocksExt2> =
+            box_dense_poly(rand_vec(1 << 4, &mut rng));
+        let comm = Pcs::commit(&pp, &*poly).unwrap();
+        let points = [(); 2].map(|_| rand_vec::<GoldilocksExt2>(4, &mut rng));
+
+        let mut accumulator = OpeningAccumulator::new();
+        for point in points.iter().chain(&points) {
+            accumulator.push(comm.clone(), point.clone(), poly.evaluate(point));
+        }
+        assert_eq!(accumulator.len(), 4);
+        assert_eq!((accumulator.comms.len(), accumulator.points.len()), (1, 2));
+        let mut transcript = StdRngTranscript::from_proof(&[]);
+        accumulator
+            .clone()
+            .settle::<_, Pcs>(&vp, &mut transcript)
+            .unwrap();
+
+        let mut transcript = StdRngTranscript::default();
+        assert!(matches!(
+            accumulator
+                .clone()
+                .open::<_, Pcs>(&pp, |_| None, &mut transcript),
+            Err(Error::InvalidPcsOpen(_))
+        ));
+
+        accumulator.push(comm, points[0].clone(), GoldilocksExt2::ONE);
+        let mut transcript = StdRngTranscript::from_proof(&[]);
+        assert!(accumulator.settle::<_, Pcs>(&vp, &mut transcript).is_err());
+    }
+}
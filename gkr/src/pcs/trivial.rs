@@ -0,0 +1,154 @@
+use crate::{
+    pcs::MultilinearPcs,
+    poly::{evaluate, DynMultilinearPoly},
+    transcript::{Transcript, TranscriptRead, TranscriptWrite},
+    util::{
+        arithmetic::{ExtensionField, Field},
+        RngCore,
+    },
+    Error,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Non-succinct scheme whose commitment is the evaluations themselves, for
+/// testing protocols against the [`MultilinearPcs`] interface.
+#[derive(Clone, Debug)]
+pub struct TrivialPcs<F, E = F>(PhantomData<(F, E)>);
+
+impl<F: Field, E: ExtensionField<F>> MultilinearPcs<F, E> for TrivialPcs<F, E> {
+    type Param = usize;
+    type ProverParam = usize;
+    type VerifierParam = usize;
+    type Commitment = Vec<F>;
+
+    fn setup(max_num_vars: usize, _: impl RngCore) -> Result<usize, Error> {
+        Ok(max_num_vars)
+    }
+
+    fn trim(param: &usize, num_vars: usize) -> Result<(usize, usize), Error> {
+        if num_vars > *param {
+            return Err(Error::InvalidPcsParam(format!(
+                "Too many variables to trim, expected at most {param} but got {num_vars}"
+            )));
+        }
+        Ok((num_vars, num_vars))
+    }
+
+    fn commit(pp: &usize, poly: &DynMultilinearPoly<F, E>) -> Result<Vec<F>, Error> {
+        if poly.num_vars() > *pp {
+            return Err(Error::InvalidPcsParam(format!(
+                "Too many variables to commit, expected at most {pp} but got {}",
+                poly.num_vars()
+            )));
+        }
+        Ok(poly.to_dense())
+    }
+
+    fn absorb_commitment(comm: &Vec<F>, transcript: &mut impl Transcript<F, E>) {
+        transcript.common_felts(comm);
+    }
+
+    fn open(
+        _: &usize,
+        _: &DynMultilinearPoly<F, E>,
+        _: &Vec<F>,
+        _: &[E],
+        _: &E,
+        _: &mut impl TranscriptWrite<F, E>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn verify(
+        _: &usize,
+        comm: &Vec<F>,
+        point: &[E],
+        eval: &E,
+        _: &mut impl TranscriptRead<F, E>,
+    ) -> Result<(), Error> {
+        if comm.len() != 1 << point.len() || evaluate(comm, point) != *eval {
+            return Err(Error::InvalidPcsOpen("Invalid evaluation".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{Evaluation, MultilinearPcs, TrivialPcs},
+        poly::{box_dense_poly, BoxMultilinearPoly},
+        transcript::StdRngTranscript,
+        util::{
+            arithmetic::Field,
+            dev::{rand_vec, seeded_std_rng},
+            Itertools,
+        },
+        Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    type Pcs = TrivialPcs<Goldilocks, GoldilocksExt2>;
+
+    #[test]
+    fn trivial_pcs() {
+        let mut rng = seeded_std_rng();
+        let num_vars = 6;
+        let param = Pcs::setup(num_vars, &mut rng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, num_vars).unwrap();
+        assert!(Pcs::trim(&param, num_vars + 1).is_err());
+
+        let polys: Vec<BoxMultilinearPoly<Goldilocks, GoldilocksExt2>> = (0..3)
+            .map(|_| box_dense_poly(rand_vec(1 << num_vars, &mut rng)))
+            .collect();
+        let polys = polys.iter().map(|poly| &**poly).collect_vec();
+        let comms = Pcs::batch_commit(&pp, &polys).unwrap();
+        let chunks = polys[0]
+            .to_dense()
+            .chunks(5)
+            .map(<[_]>::to_vec)
+            .collect_vec();
+        assert_eq!(
+            Pcs::commit_chunks(&pp, num_vars, chunks.clone()).unwrap(),
+            comms[0]
+        );
+        assert!(Pcs::commit_chunks(&pp, num_vars, chunks.iter().skip(1).cloned()).is_err());
+        assert!(Pcs::commit_chunks(&pp, num_vars, chunks.iter().chain(&chunks).cloned()).is_err());
+        assert!(Pcs::commit_chunks(&pp, num_vars + 1, chunks).is_err());
+        let points = (0..2)
+            .map(|_| rand_vec::<GoldilocksExt2>(num_vars, &mut rng))
+            .collect_vec();
+        let mut evals = [(0, 0), (1, 1), (2, 0), (2, 1)]
+            .map(|(poly, point)| Evaluation::new(poly, point, polys[poly].evaluate(&points[point])))
+            .to_vec();
+
+        let proof = {
+            let mut transcript = StdRngTranscript::default();
+            Pcs::batch_open(&pp, &polys, &comms, &points, &evals, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        Pcs::batch_verify(&vp, &comms, &points, &evals, &mut transcript).unwrap();
+
+        evals[3] = Evaluation::new(2, 1, GoldilocksExt2::ONE);
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        assert!(matches!(
+            Pcs::batch_verify(&vp, &comms, &points, &evals, &mut transcript),
+            Err(Error::InvalidPcsOpen(_))
+        ));
+
+        for (poly, point) in [(3, 0), (0, 2)] {
+            let eval = Evaluation::new(poly, point, *evals[0].value());
+            let mut transcript = StdRngTranscript::from_proof(&proof);
+            assert!(matches!(
+                Pcs::batch_verify(&vp, &comms, &points, &[eval], &mut transcript),
+                Err(Error::InvalidPcsOpen(_))
+            ));
+        }
+        let mut transcript = StdRngTranscript::default();
+        assert!(matches!(
+            Pcs::batch_open(&pp, &polys[1..], &comms, &points, &evals, &mut transcript),
+            Err(Error::InvalidPcsOpen(_))
+        ));
+    }
+}
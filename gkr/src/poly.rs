@@ -10,13 +10,21 @@ use std::{fmt::Debug, ops::Index};
 mod binary;
 mod dense;
 mod eq;
+mod interleaved;
+mod padding;
 mod repeated;
+mod shared;
+mod shifted;
 mod terms;
 
 pub use binary::BinaryMultilinearPoly;
 pub use dense::{box_dense_poly, box_owned_dense_poly, repeated_dense_poly, DensePolynomial};
 pub use eq::{eq_eval, eq_expand, eq_poly, PartialEqPoly};
+pub use interleaved::InterleavedMultilinearPoly;
+pub use padding::Padding;
 pub use repeated::RepeatedMultilinearPoly;
+pub use shared::ArcMultilinearPoly;
+pub use shifted::ShiftedMultilinearPoly;
 pub use terms::{MultilinearPolyTerms, PolyExpr};
 
 pub type DynMultilinearPoly<'a, F, E = F> = dyn MultilinearPoly<F, E> + 'a;
@@ -100,6 +108,28 @@ pub trait MultilinearPolyExt<F, E = F>: MultilinearPoly<F, E> {
     {
         RepeatedMultilinearPoly::new(self, log2_reps)
     }
+
+    fn shared<'a>(self) -> ArcMultilinearPoly<'a, F, E>
+    where
+        Self: 'a + Sized,
+    {
+        ArcMultilinearPoly::new(self)
+    }
+
+    fn shifted<'a>(self, shift: usize) -> ShiftedMultilinearPoly<Self, F, E>
+    where
+        Self: 'a + Sized,
+    {
+        ShiftedMultilinearPoly::new(self, shift)
+    }
+
+    fn interleaved<'a, P>(self, odd: P) -> InterleavedMultilinearPoly<Self, P, F, E>
+    where
+        Self: 'a + Sized,
+        P: MultilinearPoly<F, E>,
+    {
+        InterleavedMultilinearPoly::new(self, odd)
+    }
 }
 
 impl<F, E, P: MultilinearPoly<F, E>> MultilinearPolyExt<F, E> for P {}
@@ -0,0 +1,134 @@
+use crate::{
+    poly::{
+        box_owned_dense_poly, BoxMultilinearPoly, BoxMultilinearPolyOwned, MultilinearPoly,
+        MultilinearPolyExt,
+    },
+    util::arithmetic::{ExtensionField, Field},
+};
+use rayon::prelude::*;
+use std::{fmt::Debug, marker::PhantomData, ops::Index};
+
+/// Interleaving of `even` and `odd` on the least significant variable, i.e.
+/// the `2b`-th evaluation is `even[b]` and the `2b + 1`-th is `odd[b]`.
+#[derive(Clone, Debug)]
+pub struct InterleavedMultilinearPoly<T, U, F, E = F> {
+    even: T,
+    odd: U,
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F, E, T, U> InterleavedMultilinearPoly<T, U, F, E>
+where
+    T: MultilinearPoly<F, E>,
+    U: MultilinearPoly<F, E>,
+{
+    pub fn new(even: T, odd: U) -> Self {
+        assert_eq!(even.num_vars(), odd.num_vars());
+
+        Self {
+            even,
+            odd,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, E, T, U> Index<usize> for InterleavedMultilinearPoly<T, U, F, E>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: MultilinearPoly<F, E>,
+    U: MultilinearPoly<F, E>,
+{
+    type Output = F;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        debug_assert!(index < self.len());
+
+        if index & 1 == 0 {
+            &self.even[index >> 1]
+        } else {
+            &self.odd[index >> 1]
+        }
+    }
+}
+
+impl<F, E, T, U> MultilinearPoly<F, E> for InterleavedMultilinearPoly<T, U, F, E>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: MultilinearPoly<F, E>,
+    U: MultilinearPoly<F, E>,
+{
+    fn clone_box(&self) -> BoxMultilinearPoly<'_, F, E> {
+        InterleavedMultilinearPoly::new(self.even.clone_box(), self.odd.clone_box()).boxed()
+    }
+
+    fn num_vars(&self) -> usize {
+        self.even.num_vars() + 1
+    }
+
+    fn fix_var(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        let evals = (0..self.even.len())
+            .into_par_iter()
+            .with_min_len(64)
+            .map(|b| *x_i * (self.odd[b] - self.even[b]) + self.even[b])
+            .collect();
+        box_owned_dense_poly(evals)
+    }
+
+    fn fix_var_last(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        let mid = self.len() >> 1;
+        let evals = (0..mid)
+            .into_par_iter()
+            .with_min_len(64)
+            .map(|b| *x_i * (self[mid + b] - self[b]) + self[b])
+            .collect();
+        box_owned_dense_poly(evals)
+    }
+
+    fn evaluate(&self, x: &[E]) -> E {
+        assert_eq!(x.len(), self.num_vars());
+
+        let (x_0, x) = x.split_first().unwrap();
+        let (even, odd) = (self.even.evaluate(x), self.odd.evaluate(x));
+        *x_0 * (odd - even) + even
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        poly::{evaluate, DensePolynomial, MultilinearPoly, MultilinearPolyExt},
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn interleaved() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 0..8 {
+            let even = rand_vec::<Goldilocks>(1 << num_vars, &mut rng);
+            let odd = rand_vec::<Goldilocks>(1 << num_vars, &mut rng);
+            let evals = even
+                .iter()
+                .zip(&odd)
+                .flat_map(|(even, odd)| [*even, *odd])
+                .collect::<Vec<_>>();
+            let poly = MultilinearPolyExt::<_, GoldilocksExt2>::interleaved(
+                DensePolynomial::new(even.as_slice()),
+                DensePolynomial::new(odd.as_slice()),
+            );
+            assert_eq!(poly.to_dense(), evals);
+
+            let x = rand_vec::<GoldilocksExt2>(num_vars + 1, &mut rng);
+            assert_eq!(poly.evaluate(&x), evaluate(&evals, &x));
+            assert_eq!(poly.fix_var(&x[0]).evaluate(&x[1..]), evaluate(&evals, &x));
+            let (x_last, x_rest) = x.split_last().unwrap();
+            assert_eq!(
+                poly.fix_var_last(x_last).evaluate(x_rest),
+                evaluate(&evals, &x)
+            );
+        }
+    }
+}
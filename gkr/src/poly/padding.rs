@@ -0,0 +1,62 @@
+use crate::util::arithmetic::Field;
+use std::iter;
+
+/// How evaluations are padded to a power-of-two length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Padding<F> {
+    #[default]
+    Zero,
+    /// Repeats the last evaluation, or zero if there are none.
+    RepeatLast,
+    /// Pads with a designated dummy value, e.g. an entry known to be in a
+    /// lookup table whose dummy index isn't 0.
+    Value(F),
+}
+
+impl<F: Field> Padding<F> {
+    /// Pads `evals` to the next power of two.
+    pub fn pad(&self, evals: Vec<F>) -> Vec<F> {
+        let log2_size = evals.len().next_power_of_two().ilog2() as usize;
+        self.pad_to(evals, log2_size)
+    }
+
+    /// Pads `evals` to `2^log2_size`.
+    pub fn pad_to(&self, mut evals: Vec<F>, log2_size: usize) -> Vec<F> {
+        assert!(evals.len() <= 1 << log2_size);
+
+        let value = match self {
+            Self::Zero => F::ZERO,
+            Self::RepeatLast => evals.last().copied().unwrap_or(F::ZERO),
+            Self::Value(value) => *value,
+        };
+        let padding = (1 << log2_size) - evals.len();
+        evals.extend(iter::repeat_n(value, padding));
+        evals
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::poly::Padding;
+    use goldilocks::Goldilocks;
+
+    #[test]
+    fn padding() {
+        let evals = [1, 2, 3].map(Goldilocks::from).to_vec();
+        let padded = |padding: Padding<_>| padding.pad(evals.clone());
+        assert_eq!(padded(Padding::Zero), [1, 2, 3, 0].map(Goldilocks::from));
+        assert_eq!(
+            padded(Padding::RepeatLast),
+            [1, 2, 3, 3].map(Goldilocks::from)
+        );
+        assert_eq!(
+            padded(Padding::Value(Goldilocks::from(7))),
+            [1, 2, 3, 7].map(Goldilocks::from)
+        );
+        assert_eq!(
+            Padding::RepeatLast.pad_to(evals, 3)[3..],
+            [3; 5].map(Goldilocks::from)
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    poly::{BoxMultilinearPoly, BoxMultilinearPolyOwned, DynMultilinearPoly, MultilinearPoly},
+    util::arithmetic::{ExtensionField, Field},
+};
+use std::{fmt::Debug, ops::Index, sync::Arc};
+
+/// Reference-counted handle to a multilinear polynomial, cloning it only bumps
+/// the reference count so the same evaluations can be shared across nodes.
+#[derive(Debug)]
+pub struct ArcMultilinearPoly<'a, F, E = F>(Arc<DynMultilinearPoly<'a, F, E>>);
+
+impl<'a, F, E> ArcMultilinearPoly<'a, F, E> {
+    pub fn new(poly: impl MultilinearPoly<F, E> + 'a) -> Self {
+        Self(Arc::new(poly))
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<F, E> Clone for ArcMultilinearPoly<'_, F, E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, F, E> From<BoxMultilinearPoly<'a, F, E>> for ArcMultilinearPoly<'a, F, E> {
+    fn from(poly: BoxMultilinearPoly<'a, F, E>) -> Self {
+        Self(poly.into())
+    }
+}
+
+impl<F, E> Index<usize> for ArcMultilinearPoly<'_, F, E> {
+    type Output = F;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> MultilinearPoly<F, E> for ArcMultilinearPoly<'_, F, E> {
+    fn clone_box(&self) -> BoxMultilinearPoly<'_, F, E> {
+        Box::new(self.clone())
+    }
+
+    fn num_vars(&self) -> usize {
+        self.0.num_vars()
+    }
+
+    fn fix_var(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        self.0.fix_var(x_i)
+    }
+
+    fn fix_vars(&self, x: &[E]) -> BoxMultilinearPolyOwned<'static, E> {
+        self.0.fix_vars(x)
+    }
+
+    fn fix_var_last(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        self.0.fix_var_last(x_i)
+    }
+
+    fn fix_vars_last(&self, x: &[E]) -> BoxMultilinearPolyOwned<'static, E> {
+        self.0.fix_vars_last(x)
+    }
+
+    fn evaluate(&self, x: &[E]) -> E {
+        self.0.evaluate(x)
+    }
+
+    fn as_dense(&self) -> Option<&[F]> {
+        self.0.as_dense()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        poly::{box_dense_poly, evaluate, BoxMultilinearPoly, MultilinearPoly, MultilinearPolyExt},
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn shared() {
+        let mut rng = seeded_std_rng();
+        let evals = rand_vec::<Goldilocks>(1 << 6, &mut rng);
+        let poly: BoxMultilinearPoly<_, GoldilocksExt2> = box_dense_poly(evals.clone());
+        let poly = poly.shared();
+        let cloned = poly.clone();
+        let ptr = poly.as_dense().unwrap().as_ptr();
+        assert!(poly.ptr_eq(&cloned));
+        assert_eq!(cloned.as_dense().unwrap().as_ptr(), ptr);
+        assert_eq!(poly.clone_box().as_dense().unwrap().as_ptr(), ptr);
+        assert_eq!(poly.to_dense(), evals);
+
+        let x = rand_vec::<GoldilocksExt2>(6, &mut rng);
+        assert_eq!(poly.evaluate(&x), evaluate(&evals, &x));
+        assert_eq!(
+            cloned.fix_vars(&x[..3]).evaluate(&x[3..]),
+            poly.evaluate(&x)
+        );
+    }
+}
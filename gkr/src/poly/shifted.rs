@@ -0,0 +1,124 @@
+use crate::{
+    poly::{
+        box_owned_dense_poly, BoxMultilinearPoly, BoxMultilinearPolyOwned, MultilinearPoly,
+        MultilinearPolyExt,
+    },
+    util::arithmetic::{ExtensionField, Field},
+};
+use rayon::prelude::*;
+use std::{fmt::Debug, marker::PhantomData, ops::Index};
+
+/// Cyclic rotation of `inner` by `shift`, i.e. the `b`-th evaluation is the
+/// `(b + shift) mod 2^n`-th evaluation of `inner`.
+#[derive(Clone, Debug)]
+pub struct ShiftedMultilinearPoly<T, F, E = F> {
+    inner: T,
+    shift: usize,
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F, E, T: MultilinearPoly<F, E>> ShiftedMultilinearPoly<T, F, E> {
+    pub fn new(inner: T, shift: usize) -> Self {
+        let shift = shift % inner.len();
+        Self {
+            inner,
+            shift,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, E, T> Index<usize> for ShiftedMultilinearPoly<T, F, E>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: MultilinearPoly<F, E>,
+{
+    type Output = F;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        debug_assert!(index < self.len());
+
+        &self.inner[(index + self.shift) % self.inner.len()]
+    }
+}
+
+impl<F, E, T> MultilinearPoly<F, E> for ShiftedMultilinearPoly<T, F, E>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: MultilinearPoly<F, E>,
+{
+    fn clone_box(&self) -> BoxMultilinearPoly<'_, F, E> {
+        ShiftedMultilinearPoly::new(self.inner.clone_box(), self.shift).boxed()
+    }
+
+    fn num_vars(&self) -> usize {
+        self.inner.num_vars()
+    }
+
+    fn fix_var(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        let evals = (0..self.len() >> 1)
+            .into_par_iter()
+            .with_min_len(64)
+            .map(|b| *x_i * (self[2 * b + 1] - self[2 * b]) + self[2 * b])
+            .collect();
+        box_owned_dense_poly(evals)
+    }
+
+    fn fix_var_last(&self, x_i: &E) -> BoxMultilinearPolyOwned<'static, E> {
+        let mid = self.len() >> 1;
+        let evals = (0..mid)
+            .into_par_iter()
+            .with_min_len(64)
+            .map(|b| *x_i * (self[mid + b] - self[b]) + self[b])
+            .collect();
+        box_owned_dense_poly(evals)
+    }
+
+    fn evaluate(&self, x: &[E]) -> E {
+        assert_eq!(x.len(), self.num_vars());
+
+        match x.split_first() {
+            Some((x_0, x)) => self.fix_var(x_0).evaluate(x),
+            None => E::from(self[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        poly::{evaluate, DensePolynomial, MultilinearPoly, MultilinearPolyExt},
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn shifted() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 0..8 {
+            let evals = rand_vec::<Goldilocks>(1 << num_vars, &mut rng);
+            for shift in [0, 1, 3, (1 << num_vars) + 1] {
+                let rotated = (0..1 << num_vars)
+                    .map(|b| evals[(b + shift) % evals.len()])
+                    .collect::<Vec<_>>();
+                let poly = MultilinearPolyExt::<_, GoldilocksExt2>::shifted(
+                    DensePolynomial::new(evals.as_slice()),
+                    shift,
+                );
+                assert_eq!(poly.to_dense(), rotated);
+
+                let x = rand_vec::<GoldilocksExt2>(num_vars, &mut rng);
+                assert_eq!(poly.evaluate(&x), evaluate(&rotated, &x));
+                if num_vars > 0 {
+                    let (x_last, x_rest) = x.split_last().unwrap();
+                    assert_eq!(
+                        poly.fix_var_last(x_last).evaluate(x_rest),
+                        evaluate(&rotated, &x)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,17 @@
-use ff_ext::{ff::{Field, PrimeField}, ExtensionField};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use crate::{
+    util::{arithmetic::bool_to_felt, izip, parallel::parallelize},
+    Error,
+};
+use ff_ext::{
+    ff::{Field, PrimeField},
+    ExtensionField,
+};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
 
 /// Multilinear polynomials are represented as expressions
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultilinearPolyTerms<F: Field> {
     num_vars: usize,
     expression: PolyExpr<F>,
@@ -15,6 +24,24 @@ impl<F: Field> MultilinearPolyTerms<F> {
             expression,
         }
     }
+
+    /// Same as [`Self::new`], but checks that every variable is in range and that no variable
+    /// appears with degree greater than one.
+    pub fn try_new(num_vars: usize, expression: PolyExpr<F>) -> Result<Self, Error> {
+        for (var, degree) in expression.degrees() {
+            if var >= num_vars {
+                return Err(Error::InvalidPolyExpr(format!(
+                    "Variable x_{var} out of range for {num_vars} variables"
+                )));
+            }
+            if degree > 1 {
+                return Err(Error::InvalidPolyExpr(format!(
+                    "Variable x_{var} has degree {degree} but expression should be multilinear"
+                )));
+            }
+        }
+        Ok(Self::new(num_vars, expression))
+    }
 }
 
 impl<F: Field> MultilinearPolyTerms<F> {
@@ -23,12 +50,41 @@ impl<F: Field> MultilinearPolyTerms<F> {
         self.expression.evaluate(x)
     }
 
+    pub fn fix_vars<E: ExtensionField<F>>(&self, partial_point: &[E]) -> MultilinearPolyTerms<E> {
+        assert!(partial_point.len() <= self.num_vars);
+        MultilinearPolyTerms::new(
+            self.num_vars - partial_point.len(),
+            self.expression.fix_vars(partial_point),
+        )
+    }
+
+    /// Evaluates the expression over the boolean hypercube, with `x_0` as the least significant
+    /// bit of the index.
+    pub fn materialize(&self) -> Vec<F> {
+        let mut evals = vec![F::ZERO; 1 << self.num_vars];
+        parallelize(&mut evals, 64, |start, evals| {
+            let mut x = vec![F::ZERO; self.num_vars];
+            izip!(start.., evals).for_each(|(b, eval)| {
+                x.iter_mut()
+                    .enumerate()
+                    .for_each(|(i, x_i)| *x_i = bool_to_felt((b >> i) & 1 == 1));
+                *eval = self.expression.evaluate_serial(&x);
+            });
+        });
+        evals
+    }
+
     pub fn num_vars(&self) -> usize {
         self.num_vars
     }
+
+    pub fn expression(&self) -> &PolyExpr<F> {
+        &self.expression
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolyExpr<F> {
     Const(F),
     Var(usize),
@@ -41,36 +97,152 @@ impl<F: Field> PolyExpr<F> {
     pub const ZERO: PolyExpr<F> = PolyExpr::Const(F::ZERO);
     pub const ONE: PolyExpr<F> = PolyExpr::Const(F::ONE);
 
+    /// Sums and products with fewer terms than this are evaluated serially, since spawning rayon
+    /// jobs costs more than the field operations for typical table expressions.
+    pub const PAR_EVAL_THRESHOLD: usize = 256;
+
     pub fn evaluate<E: ExtensionField<F>>(&self, x: &[E]) -> E {
         match self {
-            PolyExpr::Const(c) => E::ONE * c.clone(),
-            PolyExpr::Var(i) => x[*i],
-            PolyExpr::Sum(v) => v
+            PolyExpr::Sum(v) if v.len() >= Self::PAR_EVAL_THRESHOLD => v
                 .par_iter()
                 .map(|t| t.evaluate(x))
                 .reduce(|| E::ZERO, |acc, f| acc + f),
-            PolyExpr::Prod(v) => v
+            PolyExpr::Prod(v) if v.len() >= Self::PAR_EVAL_THRESHOLD => v
                 .par_iter()
                 .map(|t| t.evaluate(x))
                 .reduce(|| E::ONE, |acc, f| acc * f),
+            PolyExpr::Sum(v) => v.iter().map(|t| t.evaluate(x)).sum(),
+            PolyExpr::Prod(v) => v.iter().map(|t| t.evaluate(x)).product(),
             PolyExpr::Pow(inner, e) => inner.evaluate(x).pow([*e as u64]),
+            _ => self.evaluate_serial(x),
+        }
+    }
+
+    pub fn evaluate_serial<E: ExtensionField<F>>(&self, x: &[E]) -> E {
+        match self {
+            PolyExpr::Const(c) => E::from(*c),
+            PolyExpr::Var(i) => x[*i],
+            PolyExpr::Sum(v) => v.iter().map(|t| t.evaluate_serial(x)).sum(),
+            PolyExpr::Prod(v) => v.iter().map(|t| t.evaluate_serial(x)).product(),
+            PolyExpr::Pow(inner, e) => inner.evaluate_serial(x).pow([*e as u64]),
+        }
+    }
+
+    /// Returns the degree of each variable appearing in the expression.
+    pub fn degrees(&self) -> BTreeMap<usize, u64> {
+        match self {
+            PolyExpr::Const(_) => BTreeMap::new(),
+            PolyExpr::Var(i) => BTreeMap::from([(*i, 1)]),
+            PolyExpr::Sum(v) => {
+                v.iter()
+                    .map(PolyExpr::degrees)
+                    .fold(BTreeMap::new(), |mut acc, degrees| {
+                        degrees.into_iter().for_each(|(var, degree)| {
+                            let acc = acc.entry(var).or_default();
+                            *acc = degree.max(*acc);
+                        });
+                        acc
+                    })
+            }
+            PolyExpr::Prod(v) => {
+                v.iter()
+                    .map(PolyExpr::degrees)
+                    .fold(BTreeMap::new(), |mut acc, degrees| {
+                        degrees
+                            .into_iter()
+                            .for_each(|(var, degree)| *acc.entry(var).or_default() += degree);
+                        acc
+                    })
+            }
+            PolyExpr::Pow(inner, e) => {
+                let mut degrees = inner.degrees();
+                degrees.values_mut().for_each(|degree| *degree *= *e as u64);
+                degrees.retain(|_, degree| *degree != 0);
+                degrees
+            }
         }
     }
 
-    pub fn add(a: Self, b: Self) -> Self{
+    /// Substitutes `x` for the first `x.len()` variables and shifts the remaining ones down.
+    pub fn fix_vars<E: ExtensionField<F>>(&self, x: &[E]) -> PolyExpr<E> {
+        match self {
+            PolyExpr::Const(c) => PolyExpr::Const(E::from(*c)),
+            PolyExpr::Var(i) if *i < x.len() => PolyExpr::Const(x[*i]),
+            PolyExpr::Var(i) => PolyExpr::Var(i - x.len()),
+            PolyExpr::Sum(v) => PolyExpr::Sum(v.iter().map(|t| t.fix_vars(x)).collect()),
+            PolyExpr::Prod(v) => PolyExpr::Prod(v.iter().map(|t| t.fix_vars(x)).collect()),
+            PolyExpr::Pow(inner, e) => PolyExpr::Pow(Box::new(inner.fix_vars(x)), *e),
+        }
+        .simplify()
+    }
+
+    /// Folds constants, flattens nested sums and products, and drops identity terms.
+    pub fn simplify(self) -> Self {
+        match self {
+            PolyExpr::Sum(v) => {
+                let mut constant = F::ZERO;
+                let mut terms = Vec::with_capacity(v.len());
+                v.into_iter().map(PolyExpr::simplify).for_each(|t| match t {
+                    PolyExpr::Const(c) => constant += c,
+                    PolyExpr::Sum(v) => terms.extend(v),
+                    t => terms.push(t),
+                });
+                if constant != F::ZERO || terms.is_empty() {
+                    terms.push(PolyExpr::Const(constant));
+                }
+                if terms.len() == 1 {
+                    terms.pop().unwrap()
+                } else {
+                    PolyExpr::Sum(terms)
+                }
+            }
+            PolyExpr::Prod(v) => {
+                let mut constant = F::ONE;
+                let mut terms = Vec::with_capacity(v.len());
+                v.into_iter().map(PolyExpr::simplify).for_each(|t| match t {
+                    PolyExpr::Const(c) => constant *= c,
+                    PolyExpr::Prod(v) => terms.extend(v),
+                    t => terms.push(t),
+                });
+                if constant == F::ZERO {
+                    return PolyExpr::Const(F::ZERO);
+                }
+                if constant != F::ONE || terms.is_empty() {
+                    terms.push(PolyExpr::Const(constant));
+                }
+                if terms.len() == 1 {
+                    terms.pop().unwrap()
+                } else {
+                    PolyExpr::Prod(terms)
+                }
+            }
+            PolyExpr::Pow(inner, e) => match (inner.simplify(), e) {
+                (_, 0) => PolyExpr::Const(F::ONE),
+                (inner, 1) => inner,
+                (PolyExpr::Const(c), e) => PolyExpr::Const(c.pow_vartime([e as u64])),
+                (inner, e) => PolyExpr::Pow(Box::new(inner), e),
+            },
+            expr => expr,
+        }
+    }
+
+    pub fn add(a: Self, b: Self) -> Self {
         PolyExpr::Sum(vec![a, b])
     }
 
-    pub fn sub(a: Self, b: Self) -> Self{
+    pub fn sub(a: Self, b: Self) -> Self {
         let neg_one = PolyExpr::Const(F::ZERO - F::ONE);
         PolyExpr::Sum(vec![a, PolyExpr::Prod(vec![b, neg_one])])
     }
 
-    pub fn mul(a: Self, b: Self) -> Self{
+    pub fn mul(a: Self, b: Self) -> Self {
         PolyExpr::Prod(vec![a, b])
     }
 
-    pub fn u64(x: u64) -> Self where F: PrimeField {
+    pub fn u64(x: u64) -> Self
+    where
+        F: PrimeField,
+    {
         PolyExpr::Const(F::from(x))
     }
 }
@@ -80,3 +252,137 @@ impl<F: Default> Default for PolyExpr<F> {
         PolyExpr::Const(F::default())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        poly::{evaluate, MultilinearPolyTerms, PolyExpr},
+        util::{
+            arithmetic::{bool_to_felt, Field},
+            dev::{rand_range, rand_vec, seeded_std_rng},
+            izip, Itertools, RngCore,
+        },
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    fn rand_poly_expr<F: Field>(
+        num_vars: usize,
+        depth: usize,
+        rng: &mut impl RngCore,
+    ) -> PolyExpr<F> {
+        let kind = if depth == 0 {
+            4
+        } else {
+            rand_range(0..5, &mut *rng)
+        };
+        let num_children = rand_range(1..4, &mut *rng);
+        let child = |rng: &mut _| rand_poly_expr(num_vars, depth.saturating_sub(1), rng);
+        match kind {
+            0 => PolyExpr::Sum((0..num_children).map(|_| child(rng)).collect()),
+            1 => PolyExpr::Prod((0..num_children).map(|_| child(rng)).collect()),
+            2 => PolyExpr::Pow(Box::new(child(rng)), 2),
+            3 => PolyExpr::Const(F::random(rng)),
+            _ => PolyExpr::Var(rand_range(0..num_vars, rng)),
+        }
+    }
+
+    #[test]
+    fn fix_vars() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 1..8 {
+            let expr = rand_poly_expr::<Goldilocks>(num_vars, 4, &mut rng);
+            let poly = MultilinearPolyTerms::new(num_vars, expr);
+            let x = rand_vec::<GoldilocksExt2>(num_vars, &mut rng);
+            for mid in 0..=num_vars {
+                let (lo, hi) = x.split_at(mid);
+                let fixed = poly.fix_vars(lo);
+                assert_eq!(fixed.num_vars(), num_vars - mid);
+                assert_eq!(fixed.evaluate(hi), poly.evaluate(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn materialize() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 1..8 {
+            let expr = rand_poly_expr::<Goldilocks>(num_vars, 4, &mut rng);
+            let poly = MultilinearPolyTerms::new(num_vars, expr);
+            izip!(0.., poly.materialize()).for_each(|(b, eval)| {
+                let x = (0..num_vars)
+                    .map(|i| bool_to_felt::<Goldilocks>((b >> i) & 1 == 1))
+                    .collect_vec();
+                assert_eq!(poly.evaluate(&x), eval);
+            });
+
+            let expr = PolyExpr::<Goldilocks>::Sum(
+                (0..num_vars)
+                    .map(|i| PolyExpr::mul(PolyExpr::u64(1 << i), PolyExpr::Var(i)))
+                    .collect(),
+            );
+            let poly = MultilinearPolyTerms::new(num_vars, expr);
+            let evals = poly.materialize();
+            assert_eq!(
+                evals,
+                (0..1 << num_vars).map(Goldilocks::from).collect_vec()
+            );
+            let x = rand_vec::<GoldilocksExt2>(num_vars, &mut rng);
+            assert_eq!(evaluate(&evals, &x), poly.evaluate(&x));
+        }
+    }
+
+    #[test]
+    fn evaluate_serial() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 1..8 {
+            let expr = rand_poly_expr::<Goldilocks>(num_vars, 4, &mut rng);
+            let x = rand_vec::<GoldilocksExt2>(num_vars, &mut rng);
+            assert_eq!(expr.evaluate_serial(&x), expr.evaluate(&x));
+        }
+
+        let x = rand_vec::<GoldilocksExt2>(4, &mut rng);
+        let wide = PolyExpr::<Goldilocks>::Sum(
+            (0..2 * PolyExpr::<Goldilocks>::PAR_EVAL_THRESHOLD)
+                .map(|i| PolyExpr::mul(PolyExpr::u64(i as u64), PolyExpr::Var(i % 4)))
+                .collect(),
+        );
+        assert_eq!(wide.evaluate_serial(&x), wide.evaluate(&x));
+    }
+
+    #[test]
+    fn try_new() {
+        let x = |i| PolyExpr::<Goldilocks>::Var(i);
+        let ok = PolyExpr::sub(
+            PolyExpr::mul(x(0), x(1)),
+            PolyExpr::add(x(2), PolyExpr::ONE),
+        );
+        assert!(MultilinearPolyTerms::try_new(3, ok.clone()).is_ok());
+        assert!(MultilinearPolyTerms::try_new(2, ok).is_err());
+
+        let squared = PolyExpr::Prod(vec![x(0), PolyExpr::add(x(0), x(1))]);
+        assert!(MultilinearPolyTerms::try_new(2, squared).is_err());
+        let squared = PolyExpr::Pow(Box::new(x(1)), 2);
+        assert!(MultilinearPolyTerms::try_new(2, squared).is_err());
+        let constant = PolyExpr::Pow(Box::new(x(1)), 0);
+        assert!(MultilinearPolyTerms::try_new(2, constant).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::poly::{MultilinearPolyTerms, PolyExpr};
+        use halo2_curves::bn256::Fr;
+
+        let expr = PolyExpr::sub(
+            PolyExpr::mul(PolyExpr::Var(0), PolyExpr::u64(1 << 16)),
+            PolyExpr::Pow(Box::new(PolyExpr::Var(1)), 1),
+        );
+        let poly = MultilinearPolyTerms::<Fr>::new(2, expr);
+
+        let bytes = serde_json::to_vec(&poly).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<MultilinearPolyTerms<Fr>>(&bytes).unwrap(),
+            poly
+        );
+    }
+}
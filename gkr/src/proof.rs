@@ -0,0 +1,317 @@
+use crate::{
+    absorb_statement, check_input_comms,
+    circuit::{node::EvalClaim, Circuit},
+    pcs::{MultilinearPcs, OpeningAccumulator},
+    poly::{BoxMultilinearPoly, DynMultilinearPoly},
+    prove_claims,
+    transcript::{TranscriptProof, TranscriptWrite},
+    util::arithmetic::{ExtensionField, Field},
+    Error,
+};
+
+/// Version 2 binds the input commitments and output claims with
+/// [`absorb_statement`] before the first challenge.
+pub const PROOF_VERSION: u32 = 2;
+
+/// Persistable GKR proof, the transcript stream split into one section per
+/// non-input node in proving order, followed by the openings of the claims on
+/// inputs made by the PCS.
+///
+/// Deserializing a proof of another version than [`PROOF_VERSION`] fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedProof")
+)]
+pub struct Proof {
+    version: u32,
+    sections: Vec<ProofSection>,
+    openings: Vec<u8>,
+}
+
+// Proof as read before its version is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedProof {
+    version: u32,
+    sections: Vec<ProofSection>,
+    openings: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedProof> for Proof {
+    type Error = String;
+
+    fn try_from(proof: UncheckedProof) -> Result<Self, String> {
+        check_version(proof.version)?;
+        Ok(Self {
+            version: proof.version,
+            sections: proof.sections,
+            openings: proof.openings,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofSection {
+    node: usize,
+    bytes: Vec<u8>,
+}
+
+impl Proof {
+    /// Runs [`prove_gkr_deferred`](crate::prove_gkr_deferred) and records the
+    /// bytes each node appends to `transcript`.
+    pub fn prove<F, E, Pcs, T>(
+        circuit: &Circuit<F, E>,
+        values: &[BoxMultilinearPoly<F, E>],
+        output_claims: &[EvalClaim<E>],
+        input_comms: &[Pcs::Commitment],
+        accumulator: &mut OpeningAccumulator<Pcs::Commitment, E>,
+        transcript: &mut T,
+    ) -> Result<Self, Error>
+    where
+        F: Field,
+        E: ExtensionField<F>,
+        Pcs: MultilinearPcs<F, E>,
+        T: TranscriptWrite<F, E> + TranscriptProof,
+    {
+        check_input_comms(circuit, input_comms)?;
+
+        absorb_statement::<F, E, Pcs>(input_comms, output_claims, transcript);
+        let mut offset = transcript.proof().len();
+        let mut sections = Vec::new();
+        let input_claims = prove_claims(
+            circuit,
+            values,
+            output_claims,
+            transcript,
+            |node, transcript| {
+                let bytes = transcript.proof()[offset..].to_vec();
+                offset += bytes.len();
+                sections.push(ProofSection { node, bytes });
+            },
+        )?;
+        accumulator.push_input_claims(input_comms, input_claims)?;
+        Ok(Self {
+            version: PROOF_VERSION,
+            sections,
+            openings: Vec::new(),
+        })
+    }
+
+    /// Opens the claims in `accumulator` with [`OpeningAccumulator::open`] on
+    /// the `transcript` passed to [`Proof::prove`], and records the bytes the
+    /// PCS appends as the openings section.
+    pub fn open<'a, F, E, Pcs, T>(
+        &mut self,
+        pp: &Pcs::ProverParam,
+        accumulator: OpeningAccumulator<Pcs::Commitment, E>,
+        poly: impl Fn(&Pcs::Commitment) -> Option<&'a DynMultilinearPoly<'a, F, E>>,
+        transcript: &mut T,
+    ) -> Result<(), Error>
+    where
+        F: Field,
+        E: ExtensionField<F>,
+        Pcs: MultilinearPcs<F, E>,
+        T: TranscriptWrite<F, E> + TranscriptProof,
+    {
+        let offset = transcript.proof().len();
+        accumulator.open::<F, Pcs>(pp, poly, transcript)?;
+        self.openings
+            .extend_from_slice(&transcript.proof()[offset..]);
+        Ok(())
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn sections(&self) -> &[ProofSection] {
+        &self.sections
+    }
+
+    pub fn openings(&self) -> &[u8] {
+        &self.openings
+    }
+
+    pub fn size(&self) -> usize {
+        self.sections
+            .iter()
+            .map(|section| section.bytes.len())
+            .sum::<usize>()
+            + self.openings.len()
+    }
+
+    /// Returns `(node, num_bytes)` for each section, without the openings.
+    pub fn size_breakdown(&self) -> Vec<(usize, usize)> {
+        self.sections
+            .iter()
+            .map(|section| (section.node, section.bytes.len()))
+            .collect()
+    }
+
+    /// Returns the transcript stream to be passed to
+    /// [`verify_gkr`](crate::verify_gkr) and then to the PCS to settle the
+    /// claims on inputs.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        check_version(self.version).map_err(Error::InvalidProof)?;
+        Ok(self
+            .sections
+            .iter()
+            .flat_map(|section| section.bytes.clone())
+            .chain(self.openings.clone())
+            .collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Proof {
+    pub fn to_bincode(&self) -> Result<Vec<u8>, Error> {
+        bincode::serialize(self).map_err(|err| Error::InvalidProof(err.to_string()))
+    }
+
+    /// Deserializes a proof written by [`Proof::to_bincode`], failing on an
+    /// unsupported version.
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, Error> {
+        bincode::deserialize(bytes).map_err(|err| Error::InvalidProof(err.to_string()))
+    }
+}
+
+fn check_version(version: u32) -> Result<(), String> {
+    if version != PROOF_VERSION {
+        return Err(format!(
+            "Unsupported proof version {version}, expected {PROOF_VERSION}"
+        ));
+    }
+    Ok(())
+}
+
+impl ProofSection {
+    pub fn node(&self) -> usize {
+        self.node
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        dev::grand_product_circuit,
+        pcs::{MultilinearPcs, OpeningAccumulator, TrivialPcs},
+        poly::MultilinearPoly,
+        proof::{Proof, PROOF_VERSION},
+        prove_gkr_deferred,
+        transcript::StdRngTranscript,
+        util::{dev::seeded_std_rng, Itertools},
+        verify_gkr_deferred, Error,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    type Pcs = TrivialPcs<Goldilocks, GoldilocksExt2>;
+
+    #[test]
+    fn proof() {
+        let mut rng = seeded_std_rng();
+        let log2_input_size = 4;
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(log2_input_size, &mut rng);
+        let output_claims = vec![EvalClaim::new(vec![], values.last().unwrap().evaluate(&[]))];
+        let (pp, vp) = Pcs::trim(
+            &Pcs::setup(log2_input_size, &mut rng).unwrap(),
+            log2_input_size,
+        )
+        .unwrap();
+        let comms = vec![Pcs::commit(&pp, &*values[0]).unwrap()];
+
+        let proof = {
+            let mut accumulator = OpeningAccumulator::new();
+            let mut transcript = StdRngTranscript::default();
+            let mut proof = Proof::prove::<_, _, Pcs, _>(
+                &circuit,
+                &values,
+                &output_claims,
+                &comms,
+                &mut accumulator,
+                &mut transcript,
+            )
+            .unwrap();
+            proof
+                .open::<_, _, Pcs, _>(&pp, accumulator, |_| Some(&*values[0]), &mut transcript)
+                .unwrap();
+            proof
+        };
+        let stream = {
+            let mut accumulator = OpeningAccumulator::new();
+            let mut transcript = StdRngTranscript::default();
+            prove_gkr_deferred::<_, _, Pcs>(
+                &circuit,
+                &values,
+                &output_claims,
+                &comms,
+                &mut accumulator,
+                &mut transcript,
+            )
+            .unwrap();
+            accumulator
+                .open::<_, Pcs>(&pp, |_| Some(&*values[0]), &mut transcript)
+                .unwrap();
+            transcript.into_proof()
+        };
+        assert_eq!(proof.to_bytes().unwrap(), stream);
+        assert_eq!(proof.size(), stream.len());
+        assert_eq!(
+            proof
+                .size_breakdown()
+                .iter()
+                .map(|(node, _)| *node)
+                .collect_vec(),
+            (1..=log2_input_size).rev().collect_vec()
+        );
+
+        let mut transcript = StdRngTranscript::from_proof(&stream);
+        let mut accumulator = OpeningAccumulator::new();
+        verify_gkr_deferred::<_, _, Pcs>(
+            &circuit,
+            &output_claims,
+            &comms,
+            &mut accumulator,
+            &mut transcript,
+        )
+        .unwrap();
+        accumulator.settle::<_, Pcs>(&vp, &mut transcript).unwrap();
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&proof).unwrap();
+            assert_eq!(serde_json::from_str::<Proof>(&json).unwrap(), proof);
+            let bincode = proof.to_bincode().unwrap();
+            assert_eq!(Proof::from_bincode(&bincode).unwrap(), proof);
+        }
+
+        let unsupported = Proof {
+            version: PROOF_VERSION + 1,
+            ..proof
+        };
+        assert!(matches!(
+            unsupported.to_bytes(),
+            Err(Error::InvalidProof(_))
+        ));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&unsupported).unwrap();
+            assert!(serde_json::from_str::<Proof>(&json).is_err());
+            let bincode = unsupported.to_bincode().unwrap();
+            assert!(matches!(
+                Proof::from_bincode(&bincode),
+                Err(Error::InvalidProof(_))
+            ));
+        }
+    }
+}
@@ -70,7 +70,11 @@ pub fn verify_sum_check<F: Field, E: ExtensionField<F>>(
     let mut r = Vec::with_capacity(num_vars);
     for round in 0..num_vars {
         let sum = g.read_round_poly(round, claim, transcript)?;
-        assert_eq!(sum.len(), degree + 1);
+        if sum.len() != degree + 1 {
+            return Err(Error::InvalidSumCheck(
+                SumCheckError::new(SumCheckPhase::Round, &(degree + 1), &sum.len()).at_round(round),
+            ));
+        }
 
         let r_i = transcript.squeeze_challenge();
 
@@ -81,8 +85,87 @@ pub fn verify_sum_check<F: Field, E: ExtensionField<F>>(
     Ok((claim, r))
 }
 
-pub fn err_unmatched_evaluation() -> Error {
-    Error::InvalidSumCheck("Unmatched evaluation from SumCheck subclaim".to_string())
+/// Step of a claim reduction at which a [`SumCheckError`] occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SumCheckPhase {
+    /// A round polynomial is malformed, with the round in
+    /// [`SumCheckError::round`].
+    Round,
+    /// The sum-check function evaluated at the final point doesn't match the
+    /// subclaim.
+    FinalEvaluation,
+    /// A check specific to the node, like the final fractional sums of
+    /// [`LogUpNode`](crate::circuit::node::LogUpNode).
+    Node(String),
+}
+
+/// Failed check of a sum-check subclaim, or of other final claims of a node,
+/// against what the verifier computes. Values are kept `Debug` formatted, so
+/// the error doesn't depend on the field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumCheckError {
+    node: Option<usize>,
+    phase: SumCheckPhase,
+    round: Option<usize>,
+    expected: String,
+    got: String,
+}
+
+impl SumCheckError {
+    pub fn new(phase: SumCheckPhase, expected: &impl Debug, got: &impl Debug) -> Self {
+        Self {
+            node: None,
+            phase,
+            round: None,
+            expected: format!("{expected:?}"),
+            got: format!("{got:?}"),
+        }
+    }
+
+    pub(crate) fn at_node(self, node: usize) -> Self {
+        Self {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    pub fn at_round(self, round: usize) -> Self {
+        Self {
+            round: Some(round),
+            ..self
+        }
+    }
+
+    /// Index of the node whose claim reduction failed, set by
+    /// [`verify_gkr`](crate::verify_gkr).
+    pub fn node(&self) -> Option<usize> {
+        self.node
+    }
+
+    pub fn phase(&self) -> &SumCheckPhase {
+        &self.phase
+    }
+
+    /// Index of the sum-check round that failed, if the error is from one.
+    pub fn round(&self) -> Option<usize> {
+        self.round
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn got(&self) -> &str {
+        &self.got
+    }
+}
+
+pub fn err_unmatched_evaluation<E: Debug>(expected: &E, got: &E) -> Error {
+    Error::InvalidSumCheck(SumCheckError::new(
+        SumCheckPhase::FinalEvaluation,
+        expected,
+        got,
+    ))
 }
 
 #[auto_impl::auto_impl(&, Box)]
@@ -327,15 +410,22 @@ where
 pub(super) mod test {
     use crate::{
         izip_eq,
-        sum_check::{prove_sum_check, verify_sum_check, BoxSumCheckPoly, SumCheckFunction},
-        transcript::StdRngTranscript,
+        poly::box_dense_poly,
+        sum_check::{
+            prove_sum_check, quadratic::Quadratic, verify_sum_check, BoxSumCheckPoly,
+            SumCheckFunction, SumCheckPhase, SumCheckPoly,
+        },
+        transcript::{StdRngTranscript, TranscriptRead, TranscriptWrite},
         util::{
-            arithmetic::{ExtensionField, PrimeField},
-            dev::seeded_std_rng,
+            arithmetic::{ExtensionField, Field, PrimeField},
+            dev::{rand_vec, seeded_std_rng},
             Itertools,
         },
+        Error,
     };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
     use rand::rngs::StdRng;
+    use std::iter;
 
     pub(super) type TestData<F, E, G> = (G, Vec<BoxSumCheckPoly<'static, F, E>>);
 
@@ -366,4 +456,83 @@ pub(super) mod test {
             });
         }
     }
+
+    // Drops the last coefficient of the round polynomial read in round `self.1`.
+    #[derive(Debug)]
+    struct Truncated<G>(G, usize);
+
+    impl<F, E: Field, G: SumCheckFunction<F, E>> SumCheckFunction<F, E> for Truncated<G> {
+        fn num_vars(&self) -> usize {
+            self.0.num_vars()
+        }
+
+        fn degree(&self) -> usize {
+            self.0.degree()
+        }
+
+        fn evaluate(&self, evals: &[E]) -> E {
+            self.0.evaluate(evals)
+        }
+
+        fn compute_sum(&self, round: usize, polys: &[BoxSumCheckPoly<F, E>]) -> E {
+            self.0.compute_sum(round, polys)
+        }
+
+        fn compute_round_poly(
+            &self,
+            round: usize,
+            claim: E,
+            polys: &[BoxSumCheckPoly<F, E>],
+        ) -> Vec<E> {
+            self.0.compute_round_poly(round, claim, polys)
+        }
+
+        fn write_round_poly(
+            &self,
+            round: usize,
+            sum: &[E],
+            transcript: &mut dyn TranscriptWrite<F, E>,
+        ) -> Result<(), Error> {
+            self.0.write_round_poly(round, sum, transcript)
+        }
+
+        fn read_round_poly(
+            &self,
+            round: usize,
+            claim: E,
+            transcript: &mut dyn TranscriptRead<F, E>,
+        ) -> Result<Vec<E>, Error> {
+            let mut sum = self.0.read_round_poly(round, claim, transcript)?;
+            if round == self.1 {
+                sum.pop();
+            }
+            Ok(sum)
+        }
+    }
+
+    #[test]
+    fn malformed_round_poly() {
+        let mut rng = seeded_std_rng();
+        let g = Quadratic::<GoldilocksExt2>::new(4, vec![(GoldilocksExt2::ONE, 0, 1)]);
+        let polys = iter::repeat_with(|| box_dense_poly(rand_vec::<Goldilocks>(1 << 4, &mut rng)))
+            .map(SumCheckPoly::Base)
+            .take(2)
+            .collect_vec();
+        let claim = SumCheckFunction::<Goldilocks, _>::compute_sum(&g, 0, &polys);
+        let proof = {
+            let polys = polys.iter().map(|poly| poly.as_ref().boxed());
+            let mut transcript = StdRngTranscript::default();
+            prove_sum_check(&g, claim, polys, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+
+        let mut transcript = StdRngTranscript::from_proof(&proof);
+        let Err(Error::InvalidSumCheck(err)) =
+            verify_sum_check::<Goldilocks, _>(&Truncated(g, 1), claim, &mut transcript)
+        else {
+            panic!("expected sum-check error");
+        };
+        assert_eq!(err.phase(), &SumCheckPhase::Round);
+        assert_eq!(err.round(), Some(1));
+    }
 }
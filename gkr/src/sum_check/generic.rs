@@ -171,7 +171,7 @@ impl<F: Field, E: ExtensionField<F>> Generic<F, E> {
     pub fn new(num_vars: usize, expression: &Expression<E, usize>) -> Self {
         let registry = ExpressionRegistry::new(expression);
         let degree = expression.degree();
-        assert!(degree >= 2);
+        assert!(degree >= 1);
         let vander_mat_inv = vander_mat_inv(steps(F::ZERO).take(degree + 1).collect());
         Self {
             num_vars,
@@ -13,6 +13,16 @@ use std::{
     iter,
 };
 
+mod keccak;
+mod poseidon2;
+mod recording;
+
+pub use keccak::{Keccak256State, Keccak256Transcript};
+pub use poseidon2::{Poseidon2, Poseidon2State, Poseidon2Transcript};
+pub use recording::{
+    first_divergence, RecordingTranscript, TranscriptEntry, TranscriptEntryKind,
+};
+
 pub trait Transcript<F, E=F>: Debug {
     fn common_felt(&mut self, felt: &F);
 
@@ -72,6 +82,20 @@ pub trait TranscriptRead<F, E=F>: Transcript<F, E> {
     }
 }
 
+/// Access to the Fiat-Shamir state, so a transcript can continue from the
+/// state of another one (e.g. a parent protocol's transcript).
+pub trait TranscriptState {
+    type State: Clone + Debug;
+
+    fn state(&self) -> Self::State;
+
+    fn set_state(&mut self, state: Self::State);
+}
+
+pub trait TranscriptProof {
+    fn proof(&self) -> &[u8];
+}
+
 pub type StdRngTranscript<S> = RngTranscript<S, StdRng>;
 
 #[derive(Debug)]
@@ -86,6 +110,24 @@ impl<P> RngTranscript<Vec<u8>, P> {
     }
 }
 
+impl<S, P: Clone + Debug> TranscriptState for RngTranscript<S, P> {
+    type State = P;
+
+    fn state(&self) -> P {
+        self.rng.clone()
+    }
+
+    fn set_state(&mut self, state: P) {
+        self.rng = state;
+    }
+}
+
+impl<P> TranscriptProof for RngTranscript<Vec<u8>, P> {
+    fn proof(&self) -> &[u8] {
+        &self.stream
+    }
+}
+
 impl<'a> RngTranscript<&'a [u8], StdRng> {
     pub fn from_proof(proof: &'a [u8]) -> Self {
         Self::new(proof)
@@ -124,13 +166,7 @@ impl<F: PrimeField, E: ExtensionField<F>, R: Debug + io::Read, P: Debug + RngCor
     TranscriptRead<F, E> for RngTranscript<R, P>
 {
     fn read_felt(&mut self) -> Result<F, Error> {
-        let mut repr = <F as PrimeField>::Repr::default();
-        self.stream
-            .read_exact(repr.as_mut())
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
-        repr.as_mut().reverse();
-        let felt = F::from_repr_vartime(repr).ok_or_else(err_invalid_felt)?;
-        Ok(felt)
+        read_felt(&mut self.stream)
     }
 
     fn read_felt_ext(&mut self) -> Result<E, Error> {
@@ -145,11 +181,7 @@ impl<F: PrimeField, E: ExtensionField<F>, W: Debug + io::Write, P: Debug + RngCo
     TranscriptWrite<F, E> for RngTranscript<W, P>
 {
     fn write_felt(&mut self, felt: &F) -> Result<(), Error> {
-        let mut repr = felt.to_repr();
-        repr.as_mut().reverse();
-        self.stream
-            .write_all(repr.as_ref())
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+        write_felt(&mut self.stream, felt)
     }
 
     fn write_felt_ext(&mut self, felt: &E) -> Result<(), Error> {
@@ -159,9 +191,66 @@ impl<F: PrimeField, E: ExtensionField<F>, W: Debug + io::Write, P: Debug + RngCo
     }
 }
 
+fn read_felt<F: PrimeField>(stream: &mut impl io::Read) -> Result<F, Error> {
+    let mut repr = <F as PrimeField>::Repr::default();
+    stream
+        .read_exact(repr.as_mut())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+    repr.as_mut().reverse();
+    let felt = F::from_repr_vartime(repr).ok_or_else(err_invalid_felt)?;
+    Ok(felt)
+}
+
+fn write_felt<F: PrimeField>(stream: &mut impl io::Write, felt: &F) -> Result<(), Error> {
+    let mut repr = felt.to_repr();
+    repr.as_mut().reverse();
+    stream
+        .write_all(repr.as_ref())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+}
+
 fn err_invalid_felt() -> Error {
     Error::Transcript(
         io::ErrorKind::Other,
         "Invalid field element read from stream".to_string(),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        transcript::{
+            Keccak256Transcript, Poseidon2, Poseidon2Transcript, StdRngTranscript, Transcript,
+            TranscriptState, TranscriptWrite,
+        },
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    fn run_continuation<T>(new: impl Fn() -> T)
+    where
+        T: TranscriptState + TranscriptWrite<Goldilocks, GoldilocksExt2>,
+    {
+        let mut rng = seeded_std_rng();
+        let felts = rand_vec::<Goldilocks>(5, &mut rng);
+
+        let mut parent = new();
+        parent.write_felts(&felts).unwrap();
+        let mut child = new();
+        child.set_state(parent.state());
+        parent.common_felt(&felts[0]);
+        child.common_felt(&felts[0]);
+        assert_eq!(
+            Transcript::<_, GoldilocksExt2>::squeeze_challenges(&mut parent, 3),
+            Transcript::<_, GoldilocksExt2>::squeeze_challenges(&mut child, 3),
+        );
+    }
+
+    #[test]
+    fn transcript_state() {
+        run_continuation(StdRngTranscript::default);
+        run_continuation(|| Keccak256Transcript::new(Vec::new()));
+        let poseidon2 = Poseidon2::<Goldilocks, 8>::goldilocks();
+        run_continuation(|| Poseidon2Transcript::new(poseidon2.clone(), Vec::new()));
+    }
+}
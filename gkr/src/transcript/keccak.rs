@@ -0,0 +1,217 @@
+use crate::{
+    transcript::{
+        read_felt, write_felt, Transcript, TranscriptProof, TranscriptRead, TranscriptState,
+        TranscriptWrite,
+    },
+    util::arithmetic::{ExtensionField, PrimeField},
+    Error,
+};
+use std::{fmt::Debug, io, iter, mem};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Keccak256 transcript whose challenges can be reproduced in Solidity.
+///
+/// Absorbed field elements are encoded as 32-byte big-endian words (as
+/// `abi.encodePacked(uint256(felt))`), and each squeeze computes
+///
+/// ```text
+/// state = keccak256(abi.encodePacked(state, absorbed...));
+/// challenge = uint256(state) % p;
+/// ```
+///
+/// starting from `state = bytes32(0)` and clearing the absorbed words. The
+/// reduction is done by Horner's rule over the four big-endian 64-bit limbs of
+/// `state`, which equals `uint256(state) % p`. A challenge in an extension
+/// field takes `E::DEGREE` consecutive squeezes as its bases.
+#[derive(Debug)]
+pub struct Keccak256Transcript<S> {
+    stream: S,
+    state: [u8; 32],
+    absorbing: Vec<u8>,
+}
+
+impl Keccak256Transcript<Vec<u8>> {
+    pub fn into_proof(self) -> Vec<u8> {
+        self.stream
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keccak256State {
+    pub state: [u8; 32],
+    pub absorbing: Vec<u8>,
+}
+
+impl<S> TranscriptState for Keccak256Transcript<S> {
+    type State = Keccak256State;
+
+    fn state(&self) -> Keccak256State {
+        Keccak256State {
+            state: self.state,
+            absorbing: self.absorbing.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: Keccak256State) {
+        self.state = state.state;
+        self.absorbing = state.absorbing;
+    }
+}
+
+impl TranscriptProof for Keccak256Transcript<Vec<u8>> {
+    fn proof(&self) -> &[u8] {
+        &self.stream
+    }
+}
+
+impl<'a> Keccak256Transcript<&'a [u8]> {
+    pub fn from_proof(proof: &'a [u8]) -> Self {
+        Self::new(proof)
+    }
+}
+
+impl<S> Keccak256Transcript<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            state: [0; 32],
+            absorbing: Vec::new(),
+        }
+    }
+
+    fn absorb<F: PrimeField>(&mut self, felt: &F) {
+        let repr = felt.to_repr();
+        let repr = repr.as_ref();
+        assert!(repr.len() <= 32);
+
+        let offset = self.absorbing.len() + 32 - repr.len();
+        self.absorbing.resize(self.absorbing.len() + 32, 0);
+        self.absorbing[offset..].copy_from_slice(repr);
+        self.absorbing[offset..].reverse();
+    }
+
+    fn squeeze_felt<F: PrimeField>(&mut self) -> F {
+        let mut hasher = Keccak::v256();
+        hasher.update(&self.state);
+        hasher.update(&mem::take(&mut self.absorbing));
+        hasher.finalize(&mut self.state);
+
+        let two_to_64 = F::from(u64::MAX) + F::ONE;
+        self.state.chunks(8).fold(F::ZERO, |acc, limb| {
+            acc * two_to_64 + F::from(u64::from_be_bytes(limb.try_into().unwrap()))
+        })
+    }
+}
+
+impl<F: PrimeField, E: ExtensionField<F>, S: Debug> Transcript<F, E> for Keccak256Transcript<S> {
+    fn common_felt(&mut self, felt: &F) {
+        self.absorb(felt);
+    }
+
+    fn squeeze_challenge(&mut self) -> E {
+        let bases = iter::repeat_with(|| self.squeeze_felt())
+            .take(E::DEGREE)
+            .collect::<Vec<_>>();
+        E::from_bases(&bases)
+    }
+}
+
+impl<F: PrimeField, E: ExtensionField<F>, R: Debug + io::Read> TranscriptRead<F, E>
+    for Keccak256Transcript<R>
+{
+    fn read_felt(&mut self) -> Result<F, Error> {
+        let felt = read_felt(&mut self.stream)?;
+        self.absorb(&felt);
+        Ok(felt)
+    }
+
+    fn read_felt_ext(&mut self) -> Result<E, Error> {
+        let bases = iter::repeat_with(|| TranscriptRead::<F, E>::read_felt(self))
+            .take(E::DEGREE)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(E::from_bases(&bases))
+    }
+}
+
+impl<F: PrimeField, E: ExtensionField<F>, W: Debug + io::Write> TranscriptWrite<F, E>
+    for Keccak256Transcript<W>
+{
+    fn write_felt(&mut self, felt: &F) -> Result<(), Error> {
+        write_felt(&mut self.stream, felt)?;
+        self.absorb(felt);
+        Ok(())
+    }
+
+    fn write_felt_ext(&mut self, felt: &E) -> Result<(), Error> {
+        felt.as_bases()
+            .iter()
+            .try_for_each(|base| TranscriptWrite::<F, E>::write_felt(self, base))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        transcript::{Keccak256Transcript, Transcript, TranscriptRead, TranscriptWrite},
+        util::dev::{rand_vec, seeded_std_rng},
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+    use halo2_curves::bn256;
+    use tiny_keccak::{Hasher, Keccak};
+
+    #[test]
+    fn keccak256_transcript() {
+        const P: u128 = 0xffffffff00000001;
+
+        let felt = Goldilocks::from(0x0123456789abcdef);
+        let mut transcript = Keccak256Transcript::new(Vec::new());
+        TranscriptWrite::<_, Goldilocks>::write_felt(&mut transcript, &felt).unwrap();
+        let challenge: Goldilocks = transcript.squeeze_challenge();
+
+        let mut word = [0; 64];
+        word[56..].copy_from_slice(&0x0123456789abcdefu64.to_be_bytes());
+        let mut state = [0; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(&word);
+        hasher.finalize(&mut state);
+        let expected = state.chunks(8).fold(0, |acc, limb| {
+            ((acc << 64) + u64::from_be_bytes(limb.try_into().unwrap()) as u128) % P
+        });
+        assert_eq!(challenge, Goldilocks::from(expected as u64));
+
+        let mut rng = seeded_std_rng();
+        let felts = rand_vec::<bn256::Fr>(5, &mut rng);
+        let (proof, challenges) = {
+            let mut transcript = Keccak256Transcript::new(Vec::new());
+            TranscriptWrite::<_, bn256::Fr>::write_felts(&mut transcript, &felts).unwrap();
+            let challenges: Vec<bn256::Fr> = transcript.squeeze_challenges(2);
+            (transcript.into_proof(), challenges)
+        };
+        let mut transcript = Keccak256Transcript::from_proof(&proof);
+        assert_eq!(
+            TranscriptRead::<_, bn256::Fr>::read_felts(&mut transcript, 5).unwrap(),
+            felts
+        );
+        assert_eq!(
+            Transcript::<_, bn256::Fr>::squeeze_challenges(&mut transcript, 2),
+            challenges
+        );
+
+        let felt_exts = rand_vec::<GoldilocksExt2>(3, &mut rng);
+        let mut transcript = Keccak256Transcript::new(Vec::new());
+        TranscriptWrite::<Goldilocks, _>::write_felt_exts(&mut transcript, &felt_exts).unwrap();
+        let challenge =
+            Transcript::<Goldilocks, GoldilocksExt2>::squeeze_challenge(&mut transcript);
+        let proof = transcript.into_proof();
+        let mut transcript = Keccak256Transcript::from_proof(&proof);
+        assert_eq!(
+            TranscriptRead::<Goldilocks, GoldilocksExt2>::read_felt_exts(&mut transcript, 3)
+                .unwrap(),
+            felt_exts
+        );
+        assert_eq!(
+            Transcript::<Goldilocks, GoldilocksExt2>::squeeze_challenge(&mut transcript),
+            challenge
+        );
+    }
+}
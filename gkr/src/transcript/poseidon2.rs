@@ -0,0 +1,501 @@
+use crate::{
+    transcript::{
+        read_felt, write_felt, Transcript, TranscriptProof, TranscriptRead, TranscriptState,
+        TranscriptWrite,
+    },
+    util::arithmetic::{ExtensionField, PrimeField},
+    Error,
+};
+use ff_ext::goldilocks::Goldilocks;
+use std::{array, fmt::Debug, io, iter, mem};
+
+/// Poseidon2 permutation over `F` with state width `T`, following the
+/// reference implementation by HorizenLabs.
+///
+/// Round constants are sampled with the Grain LFSR described in the Poseidon
+/// paper, `T` per full round and one per partial round. The internal matrix is
+/// `1 + diag(internal_diag)`.
+#[derive(Clone, Debug)]
+pub struct Poseidon2<F, const T: usize> {
+    rounds_f: usize,
+    alpha: u64,
+    internal_diag: [F; T],
+    external_constants: Vec<[F; T]>,
+    internal_constants: Vec<F>,
+}
+
+impl<F: PrimeField, const T: usize> Poseidon2<F, T> {
+    pub fn new(rounds_f: usize, rounds_p: usize, alpha: u64, internal_diag: [F; T]) -> Self {
+        assert!(T == 2 || T == 3 || T.is_multiple_of(4));
+        assert!(rounds_f.is_multiple_of(2));
+
+        let mut grain = Grain::new(F::NUM_BITS as usize, T, rounds_f, rounds_p);
+        let full_round = |grain: &mut Grain| array::from_fn(|_| grain.next_felt());
+        let mut external_constants = (0..rounds_f / 2)
+            .map(|_| full_round(&mut grain))
+            .collect::<Vec<_>>();
+        let internal_constants = (0..rounds_p).map(|_| grain.next_felt()).collect();
+        external_constants.extend((0..rounds_f / 2).map(|_| full_round(&mut grain)));
+        Self {
+            rounds_f,
+            alpha,
+            internal_diag,
+            external_constants,
+            internal_constants,
+        }
+    }
+
+    pub fn permute(&self, state: &mut [F; T]) {
+        let (first, last) = self.external_constants.split_at(self.rounds_f / 2);
+
+        self.external_layer(state);
+        first.iter().for_each(|rc| self.full_round(state, rc));
+        self.internal_constants.iter().for_each(|rc| {
+            state[0] += rc;
+            state[0] = self.sbox(state[0]);
+            self.internal_layer(state);
+        });
+        last.iter().for_each(|rc| self.full_round(state, rc));
+    }
+
+    fn full_round(&self, state: &mut [F; T], rc: &[F; T]) {
+        state.iter_mut().zip(rc).for_each(|(s, rc)| {
+            *s = self.sbox(*s + rc);
+        });
+        self.external_layer(state);
+    }
+
+    fn sbox(&self, x: F) -> F {
+        x.pow_vartime([self.alpha])
+    }
+
+    fn external_layer(&self, state: &mut [F; T]) {
+        if T < 4 {
+            let sum = state.iter().sum::<F>();
+            state.iter_mut().for_each(|s| *s += sum);
+            return;
+        }
+
+        state.chunks_exact_mut(4).for_each(m4);
+        if T == 4 {
+            return;
+        }
+
+        let sums: [F; 4] = array::from_fn(|i| state.iter().skip(i).step_by(4).sum());
+        state
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, s)| *s += sums[i % 4]);
+    }
+
+    fn internal_layer(&self, state: &mut [F; T]) {
+        let sum = state.iter().sum::<F>();
+        state
+            .iter_mut()
+            .zip(&self.internal_diag)
+            .for_each(|(s, d)| *s = *s * d + sum);
+    }
+}
+
+impl Poseidon2<Goldilocks, 8> {
+    /// Reference instance over Goldilocks with width 8.
+    pub fn goldilocks() -> Self {
+        let internal_diag = [
+            0xa98811a1fed4e3a5,
+            0x1cc48b54f377e2a0,
+            0xe40cd4f6c5609a26,
+            0x11de79ebca97a4a3,
+            0x9177c73d8b7e929c,
+            0x2a6fe8085797e791,
+            0x3de6e93329f8d5ad,
+            0x3f7af9125da962fe,
+        ];
+        Self::new(8, 22, 7, internal_diag.map(Goldilocks::from))
+    }
+}
+
+impl Poseidon2<Goldilocks, 12> {
+    /// Reference instance over Goldilocks with width 12.
+    pub fn goldilocks() -> Self {
+        let internal_diag = [
+            0xc3b6c08e23ba9300,
+            0xd84b5de94a324fb6,
+            0x0d0c371c5b35b84f,
+            0x7964f570e7188037,
+            0x5daf18bbd996604b,
+            0x6743bc47b9595257,
+            0x5528b9362c59bb70,
+            0xac45e25b7127b68b,
+            0xa2077d7dfbb606b5,
+            0xf3faac6faee378ae,
+            0x0c6388b51545e883,
+            0xd27dbb6944917b60,
+        ];
+        Self::new(8, 22, 7, internal_diag.map(Goldilocks::from))
+    }
+}
+
+impl<F: PrimeField> Poseidon2<F, 3> {
+    /// Reference instance over the BN254 scalar field with width 3, panics if
+    /// `F` is another field.
+    pub fn bn254() -> Self {
+        assert!(
+            F::MODULUS.eq_ignore_ascii_case(BN254_MODULUS),
+            "Expected BN254 scalar field"
+        );
+        Self::new(8, 56, 5, [1, 1, 2].map(F::from))
+    }
+}
+
+const BN254_MODULUS: &str = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+// Multiplication by the circulant-like 4x4 MDS matrix from the Poseidon2 paper:
+// [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]].
+fn m4<F: PrimeField>(x: &mut [F]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    x.copy_from_slice(&[t6, t5, t7, t4]);
+}
+
+struct Grain {
+    state: Vec<bool>,
+    num_bits: usize,
+}
+
+impl Grain {
+    fn new(num_bits: usize, width: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let bits = |value: usize, n: usize| (0..n).rev().map(move |i| (value >> i) & 1 == 1);
+        let state = bits(1, 2)
+            .chain(bits(0, 4))
+            .chain(bits(num_bits, 12))
+            .chain(bits(width, 12))
+            .chain(bits(rounds_f, 10))
+            .chain(bits(rounds_p, 10))
+            .chain(iter::repeat_n(true, 30))
+            .collect();
+        let mut grain = Self { state, num_bits };
+        (0..160).for_each(|_| {
+            grain.next_bit();
+        });
+        grain
+    }
+
+    fn next_bit(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.remove(0);
+        self.state.push(bit);
+        bit
+    }
+
+    fn next_filtered_bit(&mut self) -> bool {
+        loop {
+            let (b0, b1) = (self.next_bit(), self.next_bit());
+            if b0 {
+                return b1;
+            }
+        }
+    }
+
+    fn next_felt<F: PrimeField>(&mut self) -> F {
+        loop {
+            let mut bits = (0..self.num_bits)
+                .map(|_| self.next_filtered_bit())
+                .collect::<Vec<_>>();
+            bits.reverse();
+            let mut repr = F::Repr::default();
+            bits.iter()
+                .enumerate()
+                .for_each(|(i, bit)| repr.as_mut()[i / 8] |= (*bit as u8) << (i % 8));
+            if let Some(felt) = F::from_repr_vartime(repr) {
+                return felt;
+            }
+        }
+    }
+}
+
+/// Duplex sponge transcript over [`Poseidon2`] with rate `T - 1`, suitable for
+/// verification inside arithmetic circuits over `F`.
+///
+/// Written and read field elements are absorbed before any later challenge is
+/// squeezed, challenges in extension fields take `E::DEGREE` squeezed bases.
+#[derive(Debug)]
+pub struct Poseidon2Transcript<F, S, const T: usize> {
+    stream: S,
+    poseidon: Poseidon2<F, T>,
+    state: [F; T],
+    absorbing: Vec<F>,
+    squeezed: usize,
+}
+
+impl<F: PrimeField, const T: usize> Poseidon2Transcript<F, Vec<u8>, T> {
+    pub fn into_proof(self) -> Vec<u8> {
+        self.stream
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poseidon2State<F, const T: usize> {
+    pub state: [F; T],
+    pub absorbing: Vec<F>,
+    pub squeezed: usize,
+}
+
+impl<F: PrimeField, S, const T: usize> TranscriptState for Poseidon2Transcript<F, S, T> {
+    type State = Poseidon2State<F, T>;
+
+    fn state(&self) -> Self::State {
+        Poseidon2State {
+            state: self.state,
+            absorbing: self.absorbing.clone(),
+            squeezed: self.squeezed,
+        }
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        assert!(state.squeezed <= Self::RATE);
+
+        self.state = state.state;
+        self.absorbing = state.absorbing;
+        self.squeezed = state.squeezed;
+    }
+}
+
+impl<F: PrimeField, const T: usize> TranscriptProof for Poseidon2Transcript<F, Vec<u8>, T> {
+    fn proof(&self) -> &[u8] {
+        &self.stream
+    }
+}
+
+impl<'a, F: PrimeField, const T: usize> Poseidon2Transcript<F, &'a [u8], T> {
+    pub fn from_proof(poseidon: Poseidon2<F, T>, proof: &'a [u8]) -> Self {
+        Self::new(poseidon, proof)
+    }
+}
+
+impl<F: PrimeField, S, const T: usize> Poseidon2Transcript<F, S, T> {
+    const RATE: usize = T - 1;
+
+    pub fn new(poseidon: Poseidon2<F, T>, stream: S) -> Self {
+        Self {
+            stream,
+            poseidon,
+            state: [F::ZERO; T],
+            absorbing: Vec::new(),
+            squeezed: Self::RATE,
+        }
+    }
+
+    fn squeeze_felt(&mut self) -> F {
+        if !self.absorbing.is_empty() {
+            // Pads with a one, so inputs differing only in trailing zeros are
+            // absorbed into different states.
+            let mut absorbing = mem::take(&mut self.absorbing);
+            absorbing.push(F::ONE);
+            absorbing.chunks(Self::RATE).for_each(|chunk| {
+                self.state.iter_mut().zip(chunk).for_each(|(s, x)| *s += x);
+                self.poseidon.permute(&mut self.state);
+            });
+            self.squeezed = 0;
+        }
+        if self.squeezed == Self::RATE {
+            self.poseidon.permute(&mut self.state);
+            self.squeezed = 0;
+        }
+        self.squeezed += 1;
+        self.state[self.squeezed - 1]
+    }
+}
+
+impl<F, E, S, const T: usize> Transcript<F, E> for Poseidon2Transcript<F, S, T>
+where
+    F: PrimeField,
+    E: ExtensionField<F>,
+    S: Debug,
+{
+    fn common_felt(&mut self, felt: &F) {
+        self.absorbing.push(*felt);
+    }
+
+    fn squeeze_challenge(&mut self) -> E {
+        let bases = iter::repeat_with(|| self.squeeze_felt())
+            .take(E::DEGREE)
+            .collect::<Vec<_>>();
+        E::from_bases(&bases)
+    }
+}
+
+impl<F, E, R, const T: usize> TranscriptRead<F, E> for Poseidon2Transcript<F, R, T>
+where
+    F: PrimeField,
+    E: ExtensionField<F>,
+    R: Debug + io::Read,
+{
+    fn read_felt(&mut self) -> Result<F, Error> {
+        let felt = read_felt(&mut self.stream)?;
+        self.absorbing.push(felt);
+        Ok(felt)
+    }
+
+    fn read_felt_ext(&mut self) -> Result<E, Error> {
+        let bases = iter::repeat_with(|| TranscriptRead::<F, E>::read_felt(self))
+            .take(E::DEGREE)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(E::from_bases(&bases))
+    }
+}
+
+impl<F, E, W, const T: usize> TranscriptWrite<F, E> for Poseidon2Transcript<F, W, T>
+where
+    F: PrimeField,
+    E: ExtensionField<F>,
+    W: Debug + io::Write,
+{
+    fn write_felt(&mut self, felt: &F) -> Result<(), Error> {
+        write_felt(&mut self.stream, felt)?;
+        self.absorbing.push(*felt);
+        Ok(())
+    }
+
+    fn write_felt_ext(&mut self, felt: &E) -> Result<(), Error> {
+        felt.as_bases()
+            .iter()
+            .try_for_each(|base| TranscriptWrite::<F, E>::write_felt(self, base))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        transcript::{Poseidon2, Poseidon2Transcript, Transcript, TranscriptRead, TranscriptWrite},
+        util::{
+            arithmetic::{ExtensionField, Field, PrimeField},
+            dev::{rand_vec, seeded_std_rng},
+        },
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+    use halo2_curves::bn256;
+    use std::array;
+
+    fn run_transcript<F: PrimeField, E: ExtensionField<F>, const T: usize>(
+        poseidon: Poseidon2<F, T>,
+    ) {
+        let mut rng = seeded_std_rng();
+        let felts = rand_vec::<F>(10, &mut rng);
+        let felt_exts = rand_vec::<E>(3, &mut rng);
+
+        let (proof, challenges) = {
+            let mut transcript = Poseidon2Transcript::new(poseidon.clone(), Vec::new());
+            TranscriptWrite::<F, E>::write_felts(&mut transcript, &felts).unwrap();
+            let mut challenges = vec![Transcript::<F, E>::squeeze_challenge(&mut transcript)];
+            transcript.write_felt_exts(&felt_exts).unwrap();
+            challenges.extend(Transcript::<F, E>::squeeze_challenges(&mut transcript, 3));
+            (transcript.into_proof(), challenges)
+        };
+
+        let mut transcript = Poseidon2Transcript::from_proof(poseidon.clone(), &proof);
+        assert_eq!(
+            TranscriptRead::<F, E>::read_felts(&mut transcript, 10).unwrap(),
+            felts
+        );
+        let mut verifier_challenges = vec![Transcript::<F, E>::squeeze_challenge(&mut transcript)];
+        assert_eq!(
+            TranscriptRead::<F, E>::read_felt_exts(&mut transcript, 3).unwrap(),
+            felt_exts
+        );
+        verifier_challenges.extend(Transcript::<F, E>::squeeze_challenges(&mut transcript, 3));
+        assert_eq!(challenges, verifier_challenges);
+        assert!(challenges.iter().all(|challenge| *challenge != E::ZERO));
+
+        let mut tampered = proof;
+        *tampered.last_mut().unwrap() ^= 1;
+        let mut transcript = Poseidon2Transcript::from_proof(poseidon, tampered.as_slice());
+        TranscriptRead::<F, E>::read_felts(&mut transcript, 10).unwrap();
+        Transcript::<F, E>::squeeze_challenge(&mut transcript);
+        TranscriptRead::<F, E>::read_felt_exts(&mut transcript, 3).unwrap();
+        assert_ne!(
+            Transcript::<F, E>::squeeze_challenges(&mut transcript, 3),
+            challenges[1..]
+        );
+    }
+
+    fn from_hex<F: PrimeField>(hex: &str) -> F {
+        let mut repr = F::Repr::default();
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .rev()
+            .map(|idx| u8::from_str_radix(&hex[idx..idx + 2], 16).unwrap());
+        repr.as_mut()
+            .iter_mut()
+            .zip(bytes)
+            .for_each(|(lhs, rhs)| *lhs = rhs);
+        F::from_repr_vartime(repr).unwrap()
+    }
+
+    // Known answers of the HorizenLabs reference implementation on `0..T`.
+    #[test]
+    fn poseidon2_kat() {
+        let mut state = array::from_fn(|i| Goldilocks::from(i as u64));
+        Poseidon2::<Goldilocks, 8>::goldilocks().permute(&mut state);
+        let expected = [
+            "c5fb1cfe0b4697bb",
+            "4a4a32ff849af473",
+            "d2fd266077f8efba",
+            "f4ad9b74e833916d",
+            "e6648eb0acc11463",
+            "8d5529a930d75194",
+            "e8c993aa10da6c90",
+            "a73104a95b68031c",
+        ];
+        assert_eq!(state, expected.map(from_hex));
+
+        let mut state = array::from_fn(|i| Goldilocks::from(i as u64));
+        Poseidon2::<Goldilocks, 12>::goldilocks().permute(&mut state);
+        let expected = [
+            "01eaef96bdf1c0c1",
+            "1f0d2cc525b2540c",
+            "6282c1dfe1e0358d",
+            "e780d721f698e1e6",
+            "280c0b6f753d833b",
+            "1b942dd5023156ab",
+            "43f0df3fcccb8398",
+            "e8e8190585489025",
+            "56bdbf72f77ada22",
+            "7911c32bf9dcd705",
+            "ec467926508fbe67",
+            "6a50450ddf85a6ed",
+        ];
+        assert_eq!(state, expected.map(from_hex));
+
+        let mut state = array::from_fn(|i| bn256::Fr::from(i as u64));
+        Poseidon2::<bn256::Fr, 3>::bn254().permute(&mut state);
+        let expected = [
+            "0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033",
+            "303b6f7c86d043bfcbcc80214f26a30277a15d3f74ca654992defe7ff8d03570",
+            "1ed25194542b12eef8617361c3ba7c52e660b145994427cc86296242cf766ec8",
+        ];
+        assert_eq!(state, expected.map(from_hex));
+    }
+
+    #[test]
+    fn poseidon2_transcript() {
+        let goldilocks = Poseidon2::<Goldilocks, 8>::goldilocks();
+        run_transcript::<_, Goldilocks, 8>(goldilocks.clone());
+        run_transcript::<_, GoldilocksExt2, 8>(goldilocks.clone());
+        run_transcript::<_, bn256::Fr, 3>(Poseidon2::bn254());
+
+        let squeeze = |felts: &[Goldilocks]| {
+            let mut transcript = Poseidon2Transcript::new(goldilocks.clone(), Vec::new());
+            TranscriptWrite::<_, Goldilocks>::write_felts(&mut transcript, felts).unwrap();
+            Transcript::<_, Goldilocks>::squeeze_challenge(&mut transcript)
+        };
+        let a = Goldilocks::from(3);
+        assert_ne!(squeeze(&[a]), squeeze(&[a, Goldilocks::ZERO]));
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    transcript::{Transcript, TranscriptProof, TranscriptRead, TranscriptState, TranscriptWrite},
+    util::arithmetic::{ExtensionField, PrimeField},
+    Error,
+};
+use std::marker::PhantomData;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptEntryKind {
+    Common,
+    Proof,
+    Challenge,
+}
+
+/// Field elements absorbed, written/read or squeezed in a single call, with
+/// the label active at the time and the byte offset in the proof stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptEntry<F> {
+    pub label: String,
+    pub offset: usize,
+    pub kind: TranscriptEntryKind,
+    pub felts: Vec<F>,
+}
+
+/// Transcript wrapper recording every interaction, so a prover-side and a
+/// verifier-side recording can be compared with [`first_divergence`].
+#[derive(Debug)]
+pub struct RecordingTranscript<T, F, E = F> {
+    inner: T,
+    label: String,
+    offset: usize,
+    entries: Vec<TranscriptEntry<F>>,
+    _marker: PhantomData<E>,
+}
+
+impl<T, F, E> RecordingTranscript<T, F, E> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            label: String::new(),
+            offset: 0,
+            entries: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    pub fn entries(&self) -> &[TranscriptEntry<F>] {
+        &self.entries
+    }
+
+    pub fn into_inner(self) -> (T, Vec<TranscriptEntry<F>>) {
+        (self.inner, self.entries)
+    }
+}
+
+impl<T, F: PrimeField, E> RecordingTranscript<T, F, E> {
+    fn record(&mut self, kind: TranscriptEntryKind, felts: &[F]) {
+        self.entries.push(TranscriptEntry {
+            label: self.label.clone(),
+            offset: self.offset,
+            kind,
+            felts: felts.to_vec(),
+        });
+        if kind == TranscriptEntryKind::Proof {
+            self.offset += felts.len() * F::Repr::default().as_ref().len();
+        }
+    }
+}
+
+/// Returns the index of the first entry where `lhs` and `rhs` disagree on kind
+/// or values, or where one of them ends early.
+pub fn first_divergence<F: PartialEq>(
+    lhs: &[TranscriptEntry<F>],
+    rhs: &[TranscriptEntry<F>],
+) -> Option<usize> {
+    let diverged = |(lhs, rhs): (&TranscriptEntry<F>, &TranscriptEntry<F>)| {
+        lhs.kind != rhs.kind || lhs.felts != rhs.felts
+    };
+    lhs.iter()
+        .zip(rhs)
+        .position(diverged)
+        .or_else(|| (lhs.len() != rhs.len()).then(|| lhs.len().min(rhs.len())))
+}
+
+impl<T, F, E> Transcript<F, E> for RecordingTranscript<T, F, E>
+where
+    T: Transcript<F, E>,
+    F: PrimeField,
+    E: ExtensionField<F>,
+{
+    fn common_felt(&mut self, felt: &F) {
+        self.inner.common_felt(felt);
+        self.record(TranscriptEntryKind::Common, &[*felt]);
+    }
+
+    fn squeeze_challenge(&mut self) -> E {
+        let challenge = self.inner.squeeze_challenge();
+        self.record(TranscriptEntryKind::Challenge, challenge.as_bases());
+        challenge
+    }
+}
+
+impl<T, F, E> TranscriptRead<F, E> for RecordingTranscript<T, F, E>
+where
+    T: TranscriptRead<F, E>,
+    F: PrimeField,
+    E: ExtensionField<F>,
+{
+    fn read_felt(&mut self) -> Result<F, Error> {
+        let felt = self.inner.read_felt()?;
+        self.record(TranscriptEntryKind::Proof, &[felt]);
+        Ok(felt)
+    }
+
+    fn read_felt_ext(&mut self) -> Result<E, Error> {
+        let felt = self.inner.read_felt_ext()?;
+        self.record(TranscriptEntryKind::Proof, felt.as_bases());
+        Ok(felt)
+    }
+}
+
+impl<T, F, E> TranscriptWrite<F, E> for RecordingTranscript<T, F, E>
+where
+    T: TranscriptWrite<F, E>,
+    F: PrimeField,
+    E: ExtensionField<F>,
+{
+    fn write_felt(&mut self, felt: &F) -> Result<(), Error> {
+        self.inner.write_felt(felt)?;
+        self.record(TranscriptEntryKind::Proof, &[*felt]);
+        Ok(())
+    }
+
+    fn write_felt_ext(&mut self, felt: &E) -> Result<(), Error> {
+        self.inner.write_felt_ext(felt)?;
+        self.record(TranscriptEntryKind::Proof, felt.as_bases());
+        Ok(())
+    }
+}
+
+impl<T: TranscriptState, F, E> TranscriptState for RecordingTranscript<T, F, E> {
+    type State = T::State;
+
+    fn state(&self) -> T::State {
+        self.inner.state()
+    }
+
+    fn set_state(&mut self, state: T::State) {
+        self.inner.set_state(state)
+    }
+}
+
+impl<T: TranscriptProof, F, E> TranscriptProof for RecordingTranscript<T, F, E> {
+    fn proof(&self) -> &[u8] {
+        self.inner.proof()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        circuit::node::EvalClaim,
+        dev::grand_product_circuit,
+        poly::MultilinearPoly,
+        prove_gkr,
+        transcript::{
+            first_divergence, RecordingTranscript, StdRngTranscript, TranscriptEntryKind,
+        },
+        util::dev::seeded_std_rng,
+        verify_gkr,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn recording_transcript() {
+        let (circuit, values) =
+            grand_product_circuit::<Goldilocks, GoldilocksExt2>(4, &mut seeded_std_rng());
+        let output_claims = vec![EvalClaim::new(vec![], values[4].evaluate(&[]))];
+
+        let (proof, prover_entries) = {
+            let mut transcript = RecordingTranscript::new(StdRngTranscript::default());
+            prove_gkr(&circuit, &values, &output_claims, &mut transcript).unwrap();
+            let (transcript, entries) = transcript.into_inner();
+            (transcript.into_proof(), entries)
+        };
+
+        let verifier_entries = |proof: &[u8]| {
+            let mut transcript = RecordingTranscript::new(StdRngTranscript::from_proof(proof));
+            let _ = verify_gkr(&circuit, &output_claims, &mut transcript);
+            transcript.into_inner().1
+        };
+        assert_eq!(
+            first_divergence(&prover_entries, &verifier_entries(&proof)),
+            None
+        );
+
+        let tampered_offset = proof.len() / 2;
+        let mut tampered = proof.clone();
+        tampered[tampered_offset] ^= 1;
+        let idx = first_divergence(&prover_entries, &verifier_entries(&tampered)).unwrap();
+        let entry = &prover_entries[idx];
+        assert_eq!(entry.kind, TranscriptEntryKind::Proof);
+        assert!((entry.offset..entry.offset + 8 * entry.felts.len()).contains(&tampered_offset));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod arithmetic;
 pub mod collection;
 pub mod expression;
+pub mod parallel;
 
 #[cfg(any(test, feature = "dev"))]
 pub mod dev;
@@ -1,25 +1,105 @@
-use crate::util::{izip, izip_eq, Itertools};
-use rayon::{current_num_threads, prelude::*};
-use std::{borrow::Borrow, iter, mem};
+use crate::util::{izip, izip_eq, parallel::parallelize, Itertools};
+use std::{
+    borrow::Borrow,
+    iter, mem,
+    ops::{Add, Mul, Neg, Sub},
+};
 
 pub use ff_ext::{
     ff::{BatchInvert, Field, PrimeField},
     ExtensionField,
 };
 
+/// Element of `F` or of its extension `E`, kept in `F` until it's combined
+/// with an element of `E`, so arithmetic on base values avoids extension
+/// multiplications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MixedFelt<F, E> {
+    Base(F),
+    Extension(E),
+}
+
+impl<F: Field, E: ExtensionField<F>> MixedFelt<F, E> {
+    pub fn into_extension(self) -> E {
+        match self {
+            Self::Base(value) => E::from(value),
+            Self::Extension(value) => value,
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Neg for MixedFelt<F, E> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            Self::Base(value) => Self::Base(-value),
+            Self::Extension(value) => Self::Extension(-value),
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Add for MixedFelt<F, E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (Self::Base(lhs), Self::Base(rhs)) => Self::Base(lhs + rhs),
+            (Self::Base(lhs), Self::Extension(rhs)) => Self::Extension(rhs + lhs),
+            (Self::Extension(lhs), Self::Base(rhs)) => Self::Extension(lhs + rhs),
+            (Self::Extension(lhs), Self::Extension(rhs)) => Self::Extension(lhs + rhs),
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Sub for MixedFelt<F, E> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Mul for MixedFelt<F, E> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (Self::Base(lhs), Self::Base(rhs)) => Self::Base(lhs * rhs),
+            (Self::Base(lhs), Self::Extension(rhs)) => Self::Extension(rhs * lhs),
+            (Self::Extension(lhs), Self::Base(rhs)) => Self::Extension(lhs * rhs),
+            (Self::Extension(lhs), Self::Extension(rhs)) => Self::Extension(lhs * rhs),
+        }
+    }
+}
+
 pub trait ParallelBatchInvert<F: Field> {
     fn par_batch_invert(&mut self);
 }
 
 impl<F: Field> ParallelBatchInvert<F> for [F] {
     fn par_batch_invert(&mut self) {
-        let chunk_size = div_ceil(self.as_parallel_slice_mut().len(), current_num_threads());
-        self.par_chunks_mut(chunk_size).for_each(|chunk| {
+        parallelize(self, 64, |_, chunk| {
             chunk.batch_invert();
         });
     }
 }
 
+// Below this length, splitting the inversion across threads costs more than
+// the extra inversions it saves.
+const PARALLEL_BATCH_INVERT_THRESHOLD: usize = 1 << 10;
+
+/// Inverts every non-zero element of `values` in place with Montgomery's
+/// trick, leaving zeros as they are. Runs serially for short inputs and with
+/// [`ParallelBatchInvert`] for long ones.
+pub fn batch_invert<F: Field>(values: &mut [F]) {
+    if values.len() < PARALLEL_BATCH_INVERT_THRESHOLD {
+        values.batch_invert();
+    } else {
+        values.par_batch_invert();
+    }
+}
+
 pub fn div_ceil(dividend: usize, divisor: usize) -> usize {
     (dividend + divisor - 1) / divisor
 }
@@ -148,3 +228,55 @@ pub fn radix2_fft<F: Field>(buf: &mut [F], omega: F) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::util::{
+        arithmetic::{batch_invert, Field, MixedFelt, PARALLEL_BATCH_INVERT_THRESHOLD},
+        dev::{rand_vec, seeded_std_rng},
+        Itertools,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    #[test]
+    fn mixed_felt() {
+        type Mixed = MixedFelt<Goldilocks, GoldilocksExt2>;
+
+        let mut rng = seeded_std_rng();
+        let felts = [
+            Mixed::Base(Goldilocks::random(&mut rng)),
+            Mixed::Base(Goldilocks::random(&mut rng)),
+            Mixed::Extension(GoldilocksExt2::random(&mut rng)),
+            Mixed::Extension(GoldilocksExt2::random(&mut rng)),
+        ];
+        for lhs in felts {
+            assert_eq!((-lhs).into_extension(), -lhs.into_extension());
+            for rhs in felts {
+                let (lifted_lhs, lifted_rhs) = (lhs.into_extension(), rhs.into_extension());
+                assert_eq!((lhs + rhs).into_extension(), lifted_lhs + lifted_rhs);
+                assert_eq!((lhs - rhs).into_extension(), lifted_lhs - lifted_rhs);
+                assert_eq!((lhs * rhs).into_extension(), lifted_lhs * lifted_rhs);
+            }
+        }
+        assert!(matches!(felts[0] * felts[1], Mixed::Base(_)));
+        assert!(matches!(felts[0] + felts[2], Mixed::Extension(_)));
+    }
+
+    #[test]
+    fn batch_invert_with_zeros() {
+        let mut rng = seeded_std_rng();
+        for len in [0, 1, 7, PARALLEL_BATCH_INVERT_THRESHOLD + 3] {
+            let mut values = rand_vec::<Goldilocks>(len, &mut rng);
+            values
+                .iter_mut()
+                .step_by(5)
+                .for_each(|value| *value = Goldilocks::ZERO);
+            let expected = values
+                .iter()
+                .map(|value| value.invert().unwrap_or(Goldilocks::ZERO))
+                .collect_vec();
+            batch_invert(&mut values);
+            assert_eq!(values, expected);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::util::{
-    arithmetic::{powers, ExtensionField, Field},
+    arithmetic::{powers, ExtensionField, Field, MixedFelt},
     izip, Itertools,
 };
 use std::{
@@ -10,7 +10,8 @@ use std::{
     ops::{Add, Deref, Mul, Neg, Sub},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression<F, K> {
     Constant(F),
     Data(K),
@@ -29,7 +30,19 @@ impl<F, K> Expression<F, K> {
         F: Field,
         K: Clone,
     {
-        izip!(exprs, powers(base))
+        Self::linear_combination(exprs, powers(base))
+    }
+
+    /// Returns `sum_i scalars[i] * exprs[i]`, stopping at the shorter side.
+    pub fn linear_combination(
+        exprs: impl IntoIterator<Item = impl Borrow<Self>>,
+        scalars: impl IntoIterator<Item = F>,
+    ) -> Self
+    where
+        F: Field,
+        K: Clone,
+    {
+        izip!(exprs, scalars)
             .map(|(expr, scalar)| expr.borrow() * Self::constant(scalar))
             .sum()
     }
@@ -63,17 +76,40 @@ impl<F: Clone, K: Clone> Expression<F, K> {
             &|lhs, rhs| lhs + rhs,
         )
     }
+
+    pub fn map_constants<T>(&self, f: &impl Fn(F) -> T) -> Expression<T, K> {
+        self.evaluate(
+            &|constant| Expression::Constant(f(constant)),
+            &Expression::Data,
+            &|value| Expression::Neg(Box::new(value)),
+            &|lhs, rhs| Expression::Sum(Box::new(lhs), Box::new(rhs)),
+            &|lhs, rhs| Expression::Product(Box::new(lhs), Box::new(rhs)),
+        )
+    }
 }
 
 impl<F: Field, K: Clone> Expression<F, K> {
+    /// Lifts constants into the extension `E`, to combine with expressions
+    /// whose constants are challenges or weights outside `F`.
+    pub fn lift<E: ExtensionField<F>>(&self) -> Expression<E, K> {
+        self.map_constants(&E::from)
+    }
+
     pub fn evaluate_felt<E: ExtensionField<F>>(&self, data: &impl Fn(K) -> E) -> E {
+        self.evaluate_mixed(&|key| MixedFelt::Extension(data(key)))
+    }
+
+    /// Evaluates with constants and base `data` kept in `F` as long as
+    /// possible, multiplying by elements of `E` only when needed.
+    pub fn evaluate_mixed<E: ExtensionField<F>>(&self, data: &impl Fn(K) -> MixedFelt<F, E>) -> E {
         self.evaluate(
-            &|constant| E::from(constant),
+            &MixedFelt::Base,
             data,
             &|value| -value,
             &|a, b| a + b,
             &|a, b| a * b,
         )
+        .into_extension()
     }
 }
 
@@ -378,3 +414,82 @@ impl ValueSource {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::util::{
+        arithmetic::Field,
+        dev::{rand_vec, seeded_std_rng},
+        expression::Expression,
+        izip, Itertools,
+    };
+    use goldilocks::{Goldilocks, GoldilocksExt2};
+
+    fn evaluate<F: Field>(expression: &Expression<F, usize>, values: &[F]) -> F {
+        expression.evaluate(
+            &|constant| constant,
+            &|poly| values[poly],
+            &|value| -value,
+            &|lhs, rhs| lhs + rhs,
+            &|lhs, rhs| lhs * rhs,
+        )
+    }
+
+    #[test]
+    fn linear_combination() {
+        let mut rng = seeded_std_rng();
+        let values = rand_vec::<Goldilocks>(4, &mut rng);
+        let scalars = rand_vec::<Goldilocks>(3, &mut rng);
+        let exprs = (0..4).map(Expression::poly).collect_vec();
+
+        let combined = Expression::linear_combination(&exprs, scalars.clone());
+        assert_eq!(combined.degree(), 1);
+        assert_eq!(
+            evaluate(&combined, &values),
+            izip!(&values, &scalars)
+                .map(|(value, scalar)| *value * scalar)
+                .sum::<Goldilocks>()
+        );
+
+        let empty = Expression::<Goldilocks, usize>::linear_combination(&exprs, []);
+        assert_eq!(evaluate(&empty, &values), Goldilocks::ZERO);
+    }
+
+    #[test]
+    fn map_constants() {
+        let expr = (Expression::poly(0) + Expression::constant(3u64))
+            * -Expression::constant(5u64)
+            * Expression::poly(1);
+        let mapped = expr.map_constants(&|constant| constant * 2);
+        assert_eq!(
+            mapped,
+            (Expression::poly(0) + Expression::constant(6u64))
+                * -Expression::constant(10u64)
+                * Expression::poly(1)
+        );
+        assert_eq!(mapped.degree(), expr.degree());
+    }
+
+    #[test]
+    fn lift() {
+        let mut rng = seeded_std_rng();
+        let values = rand_vec::<Goldilocks>(2, &mut rng);
+        let [a, b] = [0, 1].map(Expression::<Goldilocks, _>::poly);
+        let expr = (&a - Expression::constant(Goldilocks::from(7))) * &b + -&a;
+
+        let lifted = expr.lift::<GoldilocksExt2>();
+        let lifted_values = values
+            .iter()
+            .copied()
+            .map(GoldilocksExt2::from)
+            .collect_vec();
+        assert_eq!(
+            evaluate(&lifted, &lifted_values),
+            GoldilocksExt2::from(evaluate(&expr, &values))
+        );
+        assert_eq!(
+            expr.evaluate_felt(&|poly| lifted_values[poly]),
+            evaluate(&lifted, &lifted_values)
+        );
+    }
+}
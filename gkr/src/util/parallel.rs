@@ -0,0 +1,69 @@
+use crate::util::arithmetic::div_ceil;
+use rayon::{current_num_threads, prelude::*};
+use std::cell::Cell;
+
+// Number of chunks per thread, so rayon can steal work from threads whose
+// items are slower than others'.
+const CHUNKS_PER_THREAD: usize = 8;
+
+thread_local! {
+    // Lower bound of chunk lengths on threads of a pool built by
+    // `ProverConfig::with_min_parallel_chunk`.
+    static MIN_PARALLEL_CHUNK: Cell<usize> = const { Cell::new(1) };
+}
+
+pub(crate) fn set_min_parallel_chunk(min_parallel_chunk: usize) {
+    MIN_PARALLEL_CHUNK.set(min_parallel_chunk);
+}
+
+/// Calls `f` on chunks of `v` in parallel, with the index of each chunk's first
+/// item. Chunks are at least `min_chunk_len` long, to amortize per-chunk setup
+/// like the inversion in [`BatchInvert`](crate::util::arithmetic::BatchInvert),
+/// and at least the `min_parallel_chunk` of the installed
+/// [`ProverConfig`](crate::config::ProverConfig).
+pub fn parallelize<T: Send>(
+    v: &mut [T],
+    min_chunk_len: usize,
+    f: impl Fn(usize, &mut [T]) + Send + Sync,
+) {
+    let chunk_len = div_ceil(v.len(), CHUNKS_PER_THREAD * current_num_threads())
+        .max(min_chunk_len)
+        .max(MIN_PARALLEL_CHUNK.get());
+    v.par_chunks_mut(chunk_len)
+        .enumerate()
+        .for_each(|(idx, chunk)| f(idx * chunk_len, chunk));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{parallel::parallelize, Itertools};
+    use std::sync::Mutex;
+
+    fn chunks(len: usize, min_chunk_len: usize) -> Vec<(usize, usize)> {
+        let mut v = vec![0; len];
+        let chunks = Mutex::new(Vec::new());
+        parallelize(&mut v, min_chunk_len, |start, chunk| {
+            chunk.iter_mut().for_each(|item| *item += 1);
+            chunks.lock().unwrap().push((start, chunk.len()));
+        });
+        assert!(v.iter().all(|item| *item == 1));
+        chunks.into_inner().unwrap().into_iter().sorted().collect()
+    }
+
+    #[test]
+    fn parallelize_chunks() {
+        assert_eq!(chunks(0, 4), []);
+        assert_eq!(chunks(3, 4), [(0, 3)]);
+
+        let chunks = chunks(1000, 64);
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(rest.iter().all(|(_, len)| *len == rest[0].1 && *len >= 64));
+        assert!(last.1 <= rest[0].1);
+        let mut end = 0;
+        for (start, len) in chunks {
+            assert_eq!(start, end);
+            end += len;
+        }
+        assert_eq!(end, 1000);
+    }
+}